@@ -0,0 +1,457 @@
+//! Semantic comparison of api definitions.
+
+use std::fmt::{self, Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+use swc_ecma_ast::TsKeywordTypeKind;
+
+use crate::{
+    types::{KeywordType, ObjectType, Type, TypeElement},
+    ApiClass, ApiFn, ApiParam, ProjectApis,
+};
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    /// Existing clients may stop working.
+    Breaking,
+    /// Existing clients continue to work.
+    NonBreaking,
+}
+
+/// A change of the api surface.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiChange {
+    pub kind: ChangeKind,
+
+    /// Path to the changed item, like `TodoApi.search`.
+    pub path: String,
+
+    pub message: String,
+}
+
+impl Display for ApiChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            ChangeKind::Breaking => "breaking",
+            ChangeKind::NonBreaking => "non-breaking",
+        };
+
+        write!(f, "{}: {}: {}", kind, self.path, self.message)
+    }
+}
+
+/// Returns all changes from `old` to `new`.
+pub fn diff_projects(old: &ProjectApis, new: &ProjectApis) -> Vec<ApiChange> {
+    let mut changes = vec![];
+
+    // Classes are matched by name, so moving a class to another file is not a
+    // change.
+    for old_class in old.classes() {
+        match new.classes().find(|c| c.class_name == old_class.class_name) {
+            Some(new_class) => diff_classes(&mut changes, old_class, new_class),
+            None => changes.push(ApiChange {
+                kind: ChangeKind::Breaking,
+                path: old_class.class_name.to_string(),
+                message: "api class is removed".into(),
+            }),
+        }
+    }
+
+    for new_class in new.classes() {
+        if old.classes().all(|c| c.class_name != new_class.class_name) {
+            changes.push(ApiChange {
+                kind: ChangeKind::NonBreaking,
+                path: new_class.class_name.to_string(),
+                message: "api class is added".into(),
+            });
+        }
+    }
+
+    changes
+}
+
+fn diff_classes(changes: &mut Vec<ApiChange>, old: &ApiClass, new: &ApiClass) {
+    for old_fn in old.functions.iter() {
+        let path = format!("{}.{}", old.class_name, old_fn.name);
+
+        match new.functions.iter().find(|f| f.name == old_fn.name) {
+            Some(new_fn) => diff_fns(changes, path, old_fn, new_fn),
+            None => changes.push(ApiChange {
+                kind: ChangeKind::Breaking,
+                path,
+                message: "method is removed".into(),
+            }),
+        }
+    }
+
+    for new_fn in new.functions.iter() {
+        if old.functions.iter().all(|f| f.name != new_fn.name) {
+            changes.push(ApiChange {
+                kind: ChangeKind::NonBreaking,
+                path: format!("{}.{}", new.class_name, new_fn.name),
+                message: "method is added".into(),
+            });
+        }
+    }
+}
+
+fn diff_fns<'a>(changes: &mut Vec<ApiChange>, path: String, old: &'a ApiFn, new: &'a ApiFn) {
+    if old.method != new.method {
+        changes.push(ApiChange {
+            kind: ChangeKind::Breaking,
+            path: path.clone(),
+            message: format!(
+                "http method is changed from {} to {}",
+                old.method.as_str(),
+                new.method.as_str()
+            ),
+        });
+    }
+    if old.path != new.path {
+        changes.push(ApiChange {
+            kind: ChangeKind::Breaking,
+            path: path.clone(),
+            message: "path of the route is changed".into(),
+        });
+    }
+
+    // Parameters are sent by their keys, so reordering them is not a change.
+    let old_params = old.request_params();
+    let new_params = new.request_params();
+    let find_param = |params: &[(String, &'a ApiParam)], key: &str| -> Option<&'a ApiParam> {
+        params
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, param)| *param)
+    };
+
+    for (key, old_param) in &old_params {
+        let param_path = format!("{}({})", path, key);
+
+        match find_param(&new_params, key) {
+            Some(new_param) => {
+                if old_param.location != new_param.location {
+                    changes.push(ApiChange {
+                        kind: ChangeKind::Breaking,
+                        path: param_path,
+                        message: format!(
+                            "parameter is moved from {} to {}",
+                            old_param.location.as_str(),
+                            new_param.location.as_str()
+                        ),
+                    });
+                    continue;
+                }
+
+                // Generated clients take rest parameters as separate arguments.
+                if old_param.rest != new_param.rest {
+                    changes.push(ApiChange {
+                        kind: ChangeKind::Breaking,
+                        path: param_path,
+                        message: if new_param.rest {
+                            "parameter is changed to a rest parameter".into()
+                        } else {
+                            "rest parameter is changed to a parameter".into()
+                        },
+                    });
+                    continue;
+                }
+
+                if old_param.optional != new_param.optional {
+                    changes.push(if new_param.optional {
+                        ApiChange {
+                            kind: ChangeKind::NonBreaking,
+                            path: param_path.clone(),
+                            message: "parameter is made optional".into(),
+                        }
+                    } else {
+                        ApiChange {
+                            kind: ChangeKind::Breaking,
+                            path: param_path.clone(),
+                            message: "parameter is made required".into(),
+                        }
+                    });
+                }
+
+                if old_param.validations != new_param.validations {
+                    let is_added = new_param
+                        .validations
+                        .iter()
+                        .any(|v| !old_param.validations.contains(v));
+
+                    changes.push(if is_added {
+                        ApiChange {
+                            kind: ChangeKind::Breaking,
+                            path: param_path.clone(),
+                            message: "validation is added".into(),
+                        }
+                    } else {
+                        ApiChange {
+                            kind: ChangeKind::NonBreaking,
+                            path: param_path.clone(),
+                            message: "validation is removed".into(),
+                        }
+                    });
+                }
+
+                if old_param.ty == new_param.ty {
+                    continue;
+                }
+
+                // Requests of old clients should be accepted by the new server.
+                changes.push(match check_assignable(&old_param.ty, &new_param.ty) {
+                    Ok(()) => ApiChange {
+                        kind: ChangeKind::NonBreaking,
+                        path: param_path,
+                        message: "parameter type is widened".into(),
+                    },
+                    Err(reason) => ApiChange {
+                        kind: ChangeKind::Breaking,
+                        path: param_path,
+                        message: format!("parameter type is narrowed: {}", reason),
+                    },
+                });
+            }
+            None => changes.push(ApiChange {
+                kind: ChangeKind::NonBreaking,
+                path: param_path,
+                message: "parameter is removed".into(),
+            }),
+        }
+    }
+
+    for (key, new_param) in &new_params {
+        if find_param(&old_params, key).is_none() {
+            changes.push(if new_param.optional {
+                ApiChange {
+                    kind: ChangeKind::NonBreaking,
+                    path: format!("{}({})", path, key),
+                    message: "new optional parameter is added".into(),
+                }
+            } else {
+                ApiChange {
+                    kind: ChangeKind::Breaking,
+                    path: format!("{}({})", path, key),
+                    message: "new required parameter is added".into(),
+                }
+            });
+        }
+    }
+
+    if old.return_type != new.return_type {
+        // Responses of the new server should be accepted by old clients.
+        changes.push(match check_assignable(&new.return_type, &old.return_type) {
+            Ok(()) => ApiChange {
+                kind: ChangeKind::NonBreaking,
+                path,
+                message: "return type is changed compatibly".into(),
+            },
+            Err(reason) => ApiChange {
+                kind: ChangeKind::Breaking,
+                path,
+                message: format!("return type is changed incompatibly: {}", reason),
+            },
+        });
+    }
+}
+
+/// Checks if a value of `from` is always a valid value of `to`.
+///
+/// Returns the reason if it's not.
+pub fn check_assignable(from: &Type, to: &Type) -> Result<(), String> {
+    if from == to || is_top_type(to) {
+        return Ok(());
+    }
+
+    match (from, to) {
+        (Type::Union(from), _) => {
+            for ty in from.types.iter() {
+                check_assignable(ty, to)?;
+            }
+            Ok(())
+        }
+
+        (_, Type::Union(to_union)) => {
+            if to_union
+                .types
+                .iter()
+                .any(|ty| check_assignable(from, ty).is_ok())
+            {
+                Ok(())
+            } else {
+                Err(format!(
+                    "`{}` is not assignable to `{}`",
+                    describe(from),
+                    describe(to)
+                ))
+            }
+        }
+
+        (_, Type::Intersection(to)) => {
+            for ty in to.types.iter() {
+                check_assignable(from, ty)?;
+            }
+            Ok(())
+        }
+
+        (Type::Intersection(from_intersection), _) => {
+            if from_intersection
+                .types
+                .iter()
+                .any(|ty| check_assignable(ty, to).is_ok())
+            {
+                Ok(())
+            } else {
+                Err(format!(
+                    "`{}` is not assignable to `{}`",
+                    describe(from),
+                    describe(to)
+                ))
+            }
+        }
+
+        (Type::Array(from), Type::Array(to)) => check_assignable(&from.elem, &to.elem),
+
+        (Type::Tuple(from), Type::Array(to)) => {
+            for ty in from.elems.iter() {
+                check_assignable(ty, &to.elem)?;
+            }
+            Ok(())
+        }
+
+        (Type::Tuple(from), Type::Tuple(to)) => {
+            if from.elems.len() != to.elems.len() {
+                return Err(format!(
+                    "tuple of length {} is not assignable to tuple of length {}",
+                    from.elems.len(),
+                    to.elems.len()
+                ));
+            }
+
+            for (from, to) in from.elems.iter().zip(to.elems.iter()) {
+                check_assignable(from, to)?;
+            }
+            Ok(())
+        }
+
+        (Type::Object(from), Type::Object(to)) => check_object_assignable(from, to),
+
+        _ => Err(format!(
+            "`{}` is not assignable to `{}`",
+            describe(from),
+            describe(to)
+        )),
+    }
+}
+
+fn check_object_assignable(from: &ObjectType, to: &ObjectType) -> Result<(), String> {
+    if from.builtin != to.builtin {
+        return Err(format!(
+            "`{}` is not assignable to `{}`",
+            describe_object(from),
+            describe_object(to)
+        ));
+    }
+
+    for TypeElement::Property(to_prop) in to.members.iter() {
+        let from_prop = from.members.iter().find_map(|m| match m {
+            TypeElement::Property(p) if p.name == to_prop.name => Some(p),
+            _ => None,
+        });
+
+        match from_prop {
+            Some(from_prop) => {
+                if from_prop.optional && !to_prop.optional {
+                    return Err(format!("property `{}` is optional", to_prop.name));
+                }
+
+                check_assignable(&from_prop.ty, &to_prop.ty)
+                    .map_err(|reason| format!("property `{}`: {}", to_prop.name, reason))?;
+            }
+            None => {
+                if !to_prop.optional {
+                    return Err(format!("property `{}` is missing", to_prop.name));
+                }
+            }
+        }
+    }
+
+    // Entries of `Record<string, number>` are values like properties.
+    match (&from.index_signature, &to.index_signature) {
+        (Some(from_sig), Some(to_sig)) => {
+            // Numeric keys are also strings in JSON, but not the reverse.
+            if from_sig.key == TsKeywordTypeKind::TsStringKeyword
+                && to_sig.key == TsKeywordTypeKind::TsNumberKeyword
+            {
+                return Err(format!(
+                    "index signature with `{}` keys is not assignable to one with `{}` keys",
+                    describe_keyword(from_sig.key),
+                    describe_keyword(to_sig.key)
+                ));
+            }
+
+            check_assignable(&from_sig.ty, &to_sig.ty)
+                .map_err(|reason| format!("index signature: {}", reason))?;
+        }
+        (None, Some(_)) => return Err("index signature is missing".into()),
+        (_, None) => {}
+    }
+
+    Ok(())
+}
+
+/// Returns true for `any` and `unknown`, which accept every value.
+fn is_top_type(ty: &Type) -> bool {
+    matches!(
+        ty,
+        Type::Keyword(KeywordType {
+            keyword: TsKeywordTypeKind::TsAnyKeyword | TsKeywordTypeKind::TsUnknownKeyword,
+        })
+    )
+}
+
+fn describe(ty: &Type) -> String {
+    match ty {
+        Type::Keyword(KeywordType { keyword }) => describe_keyword(*keyword),
+        Type::Array(ty) => format!("{}[]", describe(&ty.elem)),
+        Type::Tuple(ty) => format!(
+            "[{}]",
+            ty.elems.iter().map(describe).collect::<Vec<_>>().join(", ")
+        ),
+        Type::Object(ty) => describe_object(ty),
+        Type::Intersection(ty) => ty
+            .types
+            .iter()
+            .map(describe)
+            .collect::<Vec<_>>()
+            .join(" & "),
+        Type::Union(ty) => ty
+            .types
+            .iter()
+            .map(|ty| describe(ty))
+            .collect::<Vec<_>>()
+            .join(" | "),
+    }
+}
+
+fn describe_keyword(keyword: TsKeywordTypeKind) -> String {
+    match keyword {
+        TsKeywordTypeKind::TsNumberKeyword => "number".into(),
+        TsKeywordTypeKind::TsBooleanKeyword => "boolean".into(),
+        TsKeywordTypeKind::TsStringKeyword => "string".into(),
+        TsKeywordTypeKind::TsNullKeyword => "null".into(),
+        TsKeywordTypeKind::TsUndefinedKeyword => "undefined".into(),
+        TsKeywordTypeKind::TsAnyKeyword => "any".into(),
+        TsKeywordTypeKind::TsUnknownKeyword => "unknown".into(),
+        _ => format!("{:?}", keyword),
+    }
+}
+
+fn describe_object(ty: &ObjectType) -> String {
+    ty.builtin.clone().unwrap_or_else(|| "object".into())
+}
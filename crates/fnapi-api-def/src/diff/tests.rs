@@ -0,0 +1,565 @@
+use std::sync::Arc;
+
+use swc_ecma_ast::TsKeywordTypeKind;
+
+use super::{diff_projects, ApiChange, ChangeKind};
+use crate::{
+    types::{
+        ArrayType, IndexSignature, KeywordType, ObjectType, Property, Type, TypeElement, UnionType,
+    },
+    ApiClass, ApiFile, ApiFn, ApiParam, HttpMethod, ParamLocation, ProjectApis, Validation,
+};
+
+fn keyword(keyword: TsKeywordTypeKind) -> Type {
+    Type::Keyword(KeywordType { keyword })
+}
+
+fn string() -> Type {
+    keyword(TsKeywordTypeKind::TsStringKeyword)
+}
+
+fn number() -> Type {
+    keyword(TsKeywordTypeKind::TsNumberKeyword)
+}
+
+fn object(props: Vec<(&str, Type, bool)>) -> Type {
+    Type::Object(ObjectType {
+        members: props
+            .into_iter()
+            .map(|(name, ty, optional)| {
+                TypeElement::Property(Property {
+                    name: name.into(),
+                    ty: box ty,
+                    optional,
+                    description: None,
+                    examples: vec![],
+                })
+            })
+            .collect(),
+        index_signature: None,
+        builtin: None,
+    })
+}
+
+/// `Record<key, ty>`
+fn record(key: TsKeywordTypeKind, ty: Type) -> Type {
+    Type::Object(ObjectType {
+        members: vec![],
+        index_signature: Some(IndexSignature { key, ty: box ty }),
+        builtin: None,
+    })
+}
+
+fn class(name: &str, functions: Vec<(&str, Vec<Type>, Type)>) -> Arc<ApiClass> {
+    Arc::new(ApiClass {
+        class_name: name.into(),
+        is_default_export: false,
+        functions: functions
+            .into_iter()
+            .map(|(name, params, return_type)| {
+                Arc::new(ApiFn {
+                    name: name.into(),
+                    method: Default::default(),
+                    path: Default::default(),
+                    params: params
+                        .into_iter()
+                        .map(|ty| ApiParam {
+                            name: None,
+                            location: Default::default(),
+                            ty: Arc::new(ty),
+                            optional: false,
+                            rest: false,
+                            properties: vec![],
+                            validations: Default::default(),
+                            description: None,
+                        })
+                        .collect(),
+                    return_type: Arc::new(return_type),
+                    docs: Default::default(),
+                })
+            })
+            .collect(),
+    })
+}
+
+fn project(functions: Vec<(&str, Vec<Type>, Type)>) -> ProjectApis {
+    ProjectApis {
+        files: vec![Arc::new(ApiFile {
+            classes: vec![class("TodoApi", functions)],
+        })],
+    }
+}
+
+/// Modifies the last function of `project`.
+fn edit_last_fn(project: &mut ProjectApis, op: impl FnOnce(&mut ApiFn)) {
+    let file = Arc::get_mut(project.files.last_mut().unwrap()).unwrap();
+    let class = Arc::get_mut(file.classes.last_mut().unwrap()).unwrap();
+    op(Arc::get_mut(class.functions.last_mut().unwrap()).unwrap());
+}
+
+fn kinds(changes: &[ApiChange]) -> Vec<(&str, ChangeKind)> {
+    changes.iter().map(|c| (&*c.path, c.kind)).collect()
+}
+
+#[test]
+fn same() {
+    let p = project(vec![("all", vec![string()], string())]);
+
+    assert_eq!(diff_projects(&p, &p), vec![]);
+}
+
+#[test]
+fn method_removed() {
+    let old = project(vec![
+        ("all", vec![], string()),
+        ("search", vec![], string()),
+    ]);
+    let new = project(vec![("all", vec![], string())]);
+
+    assert_eq!(
+        kinds(&diff_projects(&old, &new)),
+        vec![("TodoApi.search", ChangeKind::Breaking)]
+    );
+    assert_eq!(
+        kinds(&diff_projects(&new, &old)),
+        vec![("TodoApi.search", ChangeKind::NonBreaking)]
+    );
+}
+
+#[test]
+fn param_added() {
+    let old = project(vec![("search", vec![string()], string())]);
+    let new = project(vec![("search", vec![string(), number()], string())]);
+
+    assert_eq!(
+        kinds(&diff_projects(&old, &new)),
+        vec![("TodoApi.search(p1)", ChangeKind::Breaking)]
+    );
+}
+
+#[test]
+fn optional_param_added() {
+    let old = project(vec![("search", vec![string()], string())]);
+    let mut new = project(vec![("search", vec![string(), number()], string())]);
+
+    edit_last_fn(&mut new, |f| f.params[1].optional = true);
+
+    let changes = diff_projects(&old, &new);
+    assert_eq!(
+        kinds(&changes),
+        vec![("TodoApi.search(p1)", ChangeKind::NonBreaking)]
+    );
+    assert_eq!(changes[0].message, "new optional parameter is added");
+}
+
+#[test]
+fn param_made_required() {
+    let mut optional = project(vec![("search", vec![string()], string())]);
+    edit_last_fn(&mut optional, |f| f.params[0].optional = true);
+    let required = project(vec![("search", vec![string()], string())]);
+
+    assert_eq!(
+        kinds(&diff_projects(&optional, &required)),
+        vec![("TodoApi.search(p0)", ChangeKind::Breaking)]
+    );
+    assert_eq!(
+        kinds(&diff_projects(&required, &optional)),
+        vec![("TodoApi.search(p0)", ChangeKind::NonBreaking)]
+    );
+}
+
+#[test]
+fn param_location_changed() {
+    let old = project(vec![("search", vec![string()], string())]);
+    let mut new = project(vec![("search", vec![string()], string())]);
+
+    edit_last_fn(&mut new, |f| f.params[0].location = ParamLocation::Query);
+
+    let changes = diff_projects(&old, &new);
+    assert_eq!(
+        kinds(&changes),
+        vec![("TodoApi.search(p0)", ChangeKind::Breaking)]
+    );
+    assert_eq!(changes[0].message, "parameter is moved from body to query");
+}
+
+#[test]
+fn validation_added() {
+    let old = project(vec![("search", vec![string()], string())]);
+    let mut new = project(vec![("search", vec![string()], string())]);
+    edit_last_fn(&mut new, |f| {
+        f.params[0]
+            .validations
+            .push(Validation::MinLength { length: 3 })
+    });
+
+    assert_eq!(
+        kinds(&diff_projects(&old, &new)),
+        vec![("TodoApi.search(p0)", ChangeKind::Breaking)]
+    );
+    assert_eq!(
+        kinds(&diff_projects(&new, &old)),
+        vec![("TodoApi.search(p0)", ChangeKind::NonBreaking)]
+    );
+}
+
+#[test]
+fn param_reordered() {
+    let mut old = project(vec![("search", vec![string(), number()], string())]);
+    edit_last_fn(&mut old, |f| {
+        f.params[0].name = Some("keyword".into());
+        f.params[1].name = Some("limit".into());
+    });
+    let mut new = project(vec![("search", vec![number(), string()], string())]);
+    edit_last_fn(&mut new, |f| {
+        f.params[0].name = Some("limit".into());
+        f.params[1].name = Some("keyword".into());
+    });
+
+    assert_eq!(diff_projects(&old, &new), vec![]);
+}
+
+#[test]
+fn param_renamed() {
+    let mut old = project(vec![("search", vec![string()], string())]);
+    edit_last_fn(&mut old, |f| f.params[0].name = Some("keyword".into()));
+    let mut new = project(vec![("search", vec![string()], string())]);
+    edit_last_fn(&mut new, |f| f.params[0].name = Some("query".into()));
+
+    assert_eq!(
+        kinds(&diff_projects(&old, &new)),
+        vec![
+            ("TodoApi.search(keyword)", ChangeKind::NonBreaking),
+            ("TodoApi.search(query)", ChangeKind::Breaking)
+        ]
+    );
+}
+
+#[test]
+fn destructured_property_added() {
+    let object = |names: &[&str]| {
+        names
+            .iter()
+            .map(|name| ApiParam {
+                name: Some((*name).into()),
+                location: Default::default(),
+                ty: Arc::new(string()),
+                optional: false,
+                rest: false,
+                properties: vec![],
+                validations: vec![],
+                description: None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut old = project(vec![("create", vec![string()], string())]);
+    edit_last_fn(&mut old, |f| f.params[0].properties = object(&["title"]));
+    let mut new = project(vec![("create", vec![string()], string())]);
+    edit_last_fn(&mut new, |f| {
+        f.params[0].properties = object(&["title", "memo"]);
+    });
+
+    assert_eq!(
+        kinds(&diff_projects(&old, &new)),
+        vec![("TodoApi.create(memo)", ChangeKind::Breaking)]
+    );
+}
+
+#[test]
+fn route_changed() {
+    let old = project(vec![("search", vec![], string())]);
+    let mut new = project(vec![("search", vec![], string())]);
+
+    edit_last_fn(&mut new, |f| {
+        f.method = HttpMethod::Get;
+        f.path = Some("/todos".into());
+    });
+
+    let changes = diff_projects(&old, &new);
+    assert_eq!(
+        kinds(&changes),
+        vec![
+            ("TodoApi.search", ChangeKind::Breaking),
+            ("TodoApi.search", ChangeKind::Breaking)
+        ]
+    );
+    assert_eq!(
+        changes[0].message,
+        "http method is changed from POST to GET"
+    );
+}
+
+#[test]
+fn param_type_widened() {
+    let old = project(vec![("search", vec![string()], string())]);
+    let new = project(vec![(
+        "search",
+        vec![Type::Union(UnionType {
+            types: vec![box string(), box number()],
+        })],
+        string(),
+    )]);
+
+    assert_eq!(
+        kinds(&diff_projects(&old, &new)),
+        vec![("TodoApi.search(p0)", ChangeKind::NonBreaking)]
+    );
+    assert_eq!(
+        kinds(&diff_projects(&new, &old)),
+        vec![("TodoApi.search(p0)", ChangeKind::Breaking)]
+    );
+}
+
+#[test]
+fn param_type_widened_to_unknown() {
+    let old = project(vec![("search", vec![string()], string())]);
+    let new = project(vec![(
+        "search",
+        vec![keyword(TsKeywordTypeKind::TsUnknownKeyword)],
+        string(),
+    )]);
+
+    assert_eq!(
+        kinds(&diff_projects(&old, &new)),
+        vec![("TodoApi.search(p0)", ChangeKind::NonBreaking)]
+    );
+    assert_eq!(
+        kinds(&diff_projects(&new, &old)),
+        vec![("TodoApi.search(p0)", ChangeKind::Breaking)]
+    );
+}
+
+#[test]
+fn return_type_changed_to_any() {
+    let old = project(vec![(
+        "get",
+        vec![],
+        keyword(TsKeywordTypeKind::TsAnyKeyword),
+    )]);
+    let new = project(vec![("get", vec![], string())]);
+
+    assert_eq!(
+        kinds(&diff_projects(&old, &new)),
+        vec![("TodoApi.get", ChangeKind::NonBreaking)]
+    );
+}
+
+#[test]
+fn param_made_rest() {
+    let array = || Type::Array(ArrayType { elem: box string() });
+    let old = project(vec![("tag", vec![array()], string())]);
+    let mut new = project(vec![("tag", vec![array()], string())]);
+    edit_last_fn(&mut new, |f| f.params[0].rest = true);
+
+    let changes = diff_projects(&old, &new);
+    assert_eq!(
+        kinds(&changes),
+        vec![("TodoApi.tag(p0)", ChangeKind::Breaking)]
+    );
+    assert_eq!(
+        changes[0].message,
+        "parameter is changed to a rest parameter"
+    );
+    assert_eq!(
+        kinds(&diff_projects(&new, &old)),
+        vec![("TodoApi.tag(p0)", ChangeKind::Breaking)]
+    );
+}
+
+#[test]
+fn return_type_union_narrowed() {
+    let old = project(vec![(
+        "get",
+        vec![],
+        Type::Union(UnionType {
+            types: vec![box string(), box number()],
+        }),
+    )]);
+    let new = project(vec![("get", vec![], string())]);
+
+    assert_eq!(
+        kinds(&diff_projects(&old, &new)),
+        vec![("TodoApi.get", ChangeKind::NonBreaking)]
+    );
+    assert_eq!(
+        kinds(&diff_projects(&new, &old)),
+        vec![("TodoApi.get", ChangeKind::Breaking)]
+    );
+}
+
+#[test]
+fn return_property_removed() {
+    let old = project(vec![(
+        "get",
+        vec![],
+        object(vec![("id", string(), false), ("title", string(), false)]),
+    )]);
+    let new = project(vec![("get", vec![], object(vec![("id", string(), false)]))]);
+
+    let changes = diff_projects(&old, &new);
+    assert_eq!(kinds(&changes), vec![("TodoApi.get", ChangeKind::Breaking)]);
+    assert!(changes[0].message.contains("`title` is missing"));
+}
+
+#[test]
+fn return_property_turned_optional() {
+    let old = project(vec![("get", vec![], object(vec![("id", string(), false)]))]);
+    let new = project(vec![("get", vec![], object(vec![("id", string(), true)]))]);
+
+    let changes = diff_projects(&old, &new);
+    assert_eq!(kinds(&changes), vec![("TodoApi.get", ChangeKind::Breaking)]);
+    assert!(changes[0].message.contains("`id` is optional"));
+
+    // Optional parameter properties accept more values.
+    let old = project(vec![(
+        "set",
+        vec![object(vec![("id", string(), false)])],
+        string(),
+    )]);
+    let new = project(vec![(
+        "set",
+        vec![object(vec![("id", string(), true)])],
+        string(),
+    )]);
+
+    assert_eq!(
+        kinds(&diff_projects(&old, &new)),
+        vec![("TodoApi.set(p0)", ChangeKind::NonBreaking)]
+    );
+}
+
+#[test]
+fn return_record_value_changed() {
+    let old = project(vec![(
+        "all",
+        vec![],
+        record(TsKeywordTypeKind::TsStringKeyword, number()),
+    )]);
+    let new = project(vec![(
+        "all",
+        vec![],
+        record(TsKeywordTypeKind::TsStringKeyword, string()),
+    )]);
+
+    let changes = diff_projects(&old, &new);
+    assert_eq!(kinds(&changes), vec![("TodoApi.all", ChangeKind::Breaking)]);
+    assert!(changes[0].message.contains("index signature"));
+    assert_eq!(
+        kinds(&diff_projects(&new, &old)),
+        vec![("TodoApi.all", ChangeKind::Breaking)]
+    );
+}
+
+#[test]
+fn return_record_value_widened() {
+    let old = project(vec![(
+        "all",
+        vec![],
+        record(TsKeywordTypeKind::TsStringKeyword, string()),
+    )]);
+    let new = project(vec![(
+        "all",
+        vec![],
+        record(
+            TsKeywordTypeKind::TsStringKeyword,
+            Type::Union(UnionType {
+                types: vec![box string(), box number()],
+            }),
+        ),
+    )]);
+
+    assert_eq!(
+        kinds(&diff_projects(&old, &new)),
+        vec![("TodoApi.all", ChangeKind::Breaking)]
+    );
+    assert_eq!(
+        kinds(&diff_projects(&new, &old)),
+        vec![("TodoApi.all", ChangeKind::NonBreaking)]
+    );
+}
+
+#[test]
+fn return_record_key_changed() {
+    let old = project(vec![(
+        "all",
+        vec![],
+        record(TsKeywordTypeKind::TsNumberKeyword, string()),
+    )]);
+    let new = project(vec![(
+        "all",
+        vec![],
+        record(TsKeywordTypeKind::TsStringKeyword, string()),
+    )]);
+
+    let changes = diff_projects(&old, &new);
+    assert_eq!(kinds(&changes), vec![("TodoApi.all", ChangeKind::Breaking)]);
+    assert!(changes[0].message.contains("`string` keys"));
+    assert_eq!(
+        kinds(&diff_projects(&new, &old)),
+        vec![("TodoApi.all", ChangeKind::NonBreaking)]
+    );
+}
+
+#[test]
+fn return_index_signature_removed() {
+    let old = project(vec![(
+        "all",
+        vec![],
+        record(TsKeywordTypeKind::TsStringKeyword, string()),
+    )]);
+    let new = project(vec![("all", vec![], object(vec![]))]);
+
+    let changes = diff_projects(&old, &new);
+    assert_eq!(kinds(&changes), vec![("TodoApi.all", ChangeKind::Breaking)]);
+    assert!(changes[0].message.contains("index signature is missing"));
+}
+
+#[test]
+fn docs_changed() {
+    let old = project(vec![("get", vec![], object(vec![("id", string(), false)]))]);
+    let mut new = project(vec![("get", vec![], object(vec![("id", string(), false)]))]);
+    edit_last_fn(&mut new, |f| {
+        f.docs.description = Some("Gets a todo.".into());
+        f.return_type = Arc::new(Type::Object(ObjectType {
+            members: vec![TypeElement::Property(Property {
+                name: "id".into(),
+                ty: box string(),
+                optional: false,
+                description: Some("Id of the todo.".into()),
+                examples: vec!["\"1\"".into()],
+            })],
+            index_signature: None,
+            builtin: None,
+        }));
+    });
+
+    assert_eq!(diff_projects(&old, &new), vec![]);
+}
+
+#[test]
+fn class_moved_to_another_file() {
+    let old = ProjectApis {
+        files: vec![Arc::new(ApiFile {
+            classes: vec![
+                class("UserApi", vec![("get", vec![string()], string())]),
+                class("AdminUserApi", vec![("ban", vec![string()], string())]),
+            ],
+        })],
+    };
+    let new = ProjectApis {
+        files: vec![
+            Arc::new(ApiFile {
+                classes: vec![class("UserApi", vec![("get", vec![string()], string())])],
+            }),
+            Arc::new(ApiFile {
+                classes: vec![class(
+                    "AdminUserApi",
+                    vec![("ban", vec![string()], string())],
+                )],
+            }),
+        ],
+    };
+
+    assert_eq!(diff_projects(&old, &new), vec![]);
+}
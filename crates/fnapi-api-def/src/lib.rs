@@ -4,23 +4,72 @@
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use swc_atoms::JsWord;
 
-use self::types::Type;
+use self::types::{
+    json_schema::{JsonMap, ToJsonSchema},
+    Type,
+};
 
+pub mod diff;
 pub mod types;
 
+/// Version of the format of [ApiManifest].
+///
+/// This should be bumped on each breaking change of the serialized form.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Versioned api definition of a project, written as `api.json` in the fnapi
+/// directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiManifest {
+    pub schema_version: u32,
+
+    #[serde(flatten)]
+    pub apis: ProjectApis,
+}
+
+impl ApiManifest {
+    pub fn new(apis: ProjectApis) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            apis,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProjectApis {
     pub files: Vec<Arc<ApiFile>>,
 }
 
+impl ProjectApis {
+    /// Returns api classes of all files.
+    pub fn classes(&self) -> impl Iterator<Item = &Arc<ApiClass>> {
+        self.files.iter().flat_map(|file| file.classes.iter())
+    }
+}
+
 /// This struct contains enough information to generate client for a api file.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiFile {
+    pub classes: Vec<Arc<ApiClass>>,
+}
+
+/// An exported class with api methods.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiClass {
     pub class_name: JsWord,
+
+    /// `true` for `export default class`.
+    #[serde(default)]
+    pub is_default_export: bool,
+
     pub functions: Vec<Arc<ApiFn>>,
 }
 
@@ -29,14 +78,254 @@ pub struct ApiFile {
 pub struct ApiFn {
     pub name: JsWord,
 
+    /// Set by `@Http`, or `httpMethod` of `@FnApi`.
+    #[serde(default)]
+    pub method: HttpMethod,
+
+    /// Path template set by `@Http`, like `/todos/:id`. Path parameters are
+    /// named after the key of the bound parameter.
+    ///
+    /// [None] means `/{ClassName}/{name}`, followed by path parameters.
+    #[serde(default)]
+    pub path: Option<String>,
+
     pub params: Vec<ApiParam>,
 
     pub return_type: Arc<Type>,
+
+    /// From the JSDoc comment of the method.
+    #[serde(default)]
+    pub docs: Docs,
+}
+
+/// Documentation of an api function, from its JSDoc comment.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Docs {
+    /// Text before the block tags.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Set by `@deprecated`, with the reason. The reason may be empty.
+    #[serde(default)]
+    pub deprecated: Option<String>,
+
+    /// Code of `@example` tags.
+    #[serde(default)]
+    pub examples: Vec<String>,
+}
+
+impl Docs {
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none() && self.deprecated.is_none() && self.examples.is_empty()
+    }
+}
+
+impl ApiFn {
+    /// Returns the path template of the route, like `/api/TodoApi/get/:id`.
+    pub fn route(&self, prefix: Option<&str>, class_name: &str) -> String {
+        let prefix = prefix.unwrap_or_default();
+
+        match &self.path {
+            Some(path) => format!("{}{}", prefix, path),
+            None => {
+                let path_params = self
+                    .request_params()
+                    .into_iter()
+                    .filter(|(_, param)| param.location == ParamLocation::Path)
+                    .map(|(key, _)| format!("/:{}", key))
+                    .collect::<String>();
+
+                format!("{}/{}/{}{}", prefix, class_name, self.name, path_params)
+            }
+        }
+    }
+
+    /// Returns the parameters sent in requests, with their keys.
+    ///
+    /// Properties of destructured parameters are sent instead of the
+    /// parameters.
+    pub fn request_params(&self) -> Vec<(String, &ApiParam)> {
+        self.params
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, param)| {
+                if param.properties.is_empty() {
+                    vec![(param.key(idx), param)]
+                } else {
+                    param
+                        .properties
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, prop)| (prop.key(idx), prop))
+                        .collect()
+                }
+            })
+            .collect()
+    }
+
+    /// Returns an object schema of the parameters at `location`, keyed by
+    /// the keys of [ApiFn::request_params], or [None] if there's no such
+    /// parameter.
+    pub fn params_schema(&self, location: ParamLocation) -> Option<JsonMap> {
+        let params = self
+            .request_params()
+            .into_iter()
+            .filter(|(_, param)| param.location == location)
+            .collect::<Vec<_>>();
+
+        if params.is_empty() {
+            return None;
+        }
+
+        let properties = params
+            .iter()
+            .map(|(key, param)| (key.clone(), Value::Object(param.to_json_schema())))
+            .collect::<Map<_, _>>();
+        let required = params
+            .iter()
+            .filter(|(_, param)| param.is_required())
+            .map(|(key, _)| Value::String(key.clone()))
+            .collect::<Vec<_>>();
+
+        let mut schema = JsonMap::default();
+        schema.insert("type".into(), Value::String("object".into()));
+        schema.insert("properties".into(), Value::Object(properties));
+        if !required.is_empty() {
+            schema.insert("required".into(), Value::Array(required));
+        }
+        Some(schema)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiParam {
+    /// Name in the signature. This is [None] for destructured parameters.
     pub name: Option<JsWord>,
+
+    /// Part of the request which carries the parameter.
+    #[serde(default)]
+    pub location: ParamLocation,
+
     pub ty: Arc<Type>,
+
+    /// `true` if the parameter may be omitted, like `limit?: number` or
+    /// `limit = 10`.
+    #[serde(default)]
+    pub optional: bool,
+
+    /// `true` for rest parameters like `...tags: string[]`, which are sent as
+    /// an array.
+    #[serde(default)]
+    pub rest: bool,
+
+    /// Properties of a destructured parameter like `{ title }: CreateTodo`,
+    /// which are sent as parameters.
+    #[serde(default)]
+    pub properties: Vec<ApiParam>,
+
+    /// Rules from `@Validate`, which are checked by both of the server and
+    /// the clients.
+    #[serde(default)]
+    pub validations: Vec<Validation>,
+
+    /// From the `@param` tag of the method, like `@param title - The title`.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+impl ApiParam {
+    /// Returns the key of the parameter in requests, which is the name, or
+    /// `p{idx}` if the parameter does not have a name.
+    pub fn key(&self, idx: usize) -> String {
+        match &self.name {
+            Some(name) => name.to_string(),
+            None => format!("p{}", idx),
+        }
+    }
+
+    /// Returns `true` if requests must carry the parameter, which is the case
+    /// for optional parameters validated by `validators.required` too.
+    pub fn is_required(&self) -> bool {
+        !self.optional || self.validations.contains(&Validation::Required)
+    }
+}
+
+/// A validator of `@fnapi/api/validators` known to the compiler.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum Validation {
+    /// `validators.required`
+    Required,
+    /// `validators.email`
+    Email,
+    /// `validators.minLength(length)`
+    MinLength { length: u32 },
+    /// `validators.maxLength(length)`
+    MaxLength { length: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    Get,
+    #[default]
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl HttpMethod {
+    pub const ALL: [HttpMethod; 5] = [
+        HttpMethod::Get,
+        HttpMethod::Post,
+        HttpMethod::Put,
+        HttpMethod::Patch,
+        HttpMethod::Delete,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Delete => "DELETE",
+        }
+    }
+
+    /// Parses a method name, ignoring the case.
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|method| method.as_str().eq_ignore_ascii_case(s))
+    }
+}
+
+/// Part of a request, selected by parameter decorators like `@Query()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ParamLocation {
+    /// The JSON body. This is the default.
+    #[default]
+    Body,
+    /// The query string.
+    Query,
+    /// A segment of the url, following the name of the function.
+    Path,
+    Header,
+}
+
+impl ParamLocation {
+    /// Name used by OpenAPI and the generated code.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ParamLocation::Body => "body",
+            ParamLocation::Query => "query",
+            ParamLocation::Path => "path",
+            ParamLocation::Header => "header",
+        }
+    }
 }
@@ -7,6 +7,7 @@ use super::{
     ArrayType, IntersectionType, KeywordType, ObjectType, Property, TupleType, Type, TypeElement,
     UnionType,
 };
+use crate::{ApiParam, Validation};
 
 pub type JsonMap = Map<String, Value>;
 
@@ -26,6 +27,46 @@ pub trait ToJsonSchema {
     }
 }
 
+impl ToJsonSchema for JsonMap {
+    fn to_json_schema(&self) -> JsonMap {
+        self.clone()
+    }
+}
+
+/// Schema of the type, with keywords for the validations.
+impl ToJsonSchema for ApiParam {
+    fn to_json_schema(&self) -> JsonMap {
+        let mut map = self.ty.to_json_schema();
+
+        if let Some(description) = &self.description {
+            map.insert("description".into(), Value::String(description.clone()));
+        }
+
+        for validation in &self.validations {
+            match validation {
+                // This matches `validators.required`, which rejects empty
+                // strings. Other types are checked by `required`.
+                Validation::Required => {
+                    if map.get("type") == Some(&Value::String("string".into())) {
+                        map.entry("minLength").or_insert_with(|| Value::from(1));
+                    }
+                }
+                Validation::Email => {
+                    map.insert("format".into(), Value::String("email".into()));
+                }
+                Validation::MinLength { length } => {
+                    map.insert("minLength".into(), Value::from(*length));
+                }
+                Validation::MaxLength { length } => {
+                    map.insert("maxLength".into(), Value::from(*length));
+                }
+            }
+        }
+
+        map
+    }
+}
+
 impl ToJsonSchema for Type {
     fn to_json_schema(&self) -> JsonMap {
         match self {
@@ -90,6 +131,17 @@ impl ToJsonSchema for TupleType {
 impl ToJsonSchema for ObjectType {
     fn to_json_schema(&self) -> JsonMap {
         let mut map = Map::default();
+
+        if let Some(builtin) = &self.builtin {
+            // `Date` is sent as a string by `toJSON`. Other builtins are not
+            // checked.
+            if builtin == "Date" {
+                map.insert("type".into(), Value::String("string".into()));
+                map.insert("format".into(), Value::String("date-time".into()));
+            }
+            return map;
+        }
+
         map.insert("type".into(), Value::String("object".into()));
 
         map.insert(
@@ -120,6 +172,14 @@ impl ToJsonSchema for ObjectType {
             }
         }
         map.insert("properties".into(), Value::Object(properties));
+
+        // Without this, fastify removes the entries of `Record<string, Todo>`.
+        if let Some(sig) = &self.index_signature {
+            map.insert(
+                "additionalProperties".into(),
+                Value::Object(sig.ty.to_json_schema()),
+            );
+        }
         map
     }
 }
@@ -166,7 +226,26 @@ impl ToJsonSchema for UnionType {
 
 impl ToJsonSchema for Property {
     fn to_json_schema(&self) -> JsonMap {
-        self.ty.to_json_schema()
+        let mut map = self.ty.to_json_schema();
+        if let Some(description) = &self.description {
+            map.insert("description".into(), Value::String(description.clone()));
+        }
+        if !self.examples.is_empty() {
+            map.insert(
+                "examples".into(),
+                // Examples written as JSON, like `@example "1"`, are values.
+                Value::Array(
+                    self.examples
+                        .iter()
+                        .map(|example| {
+                            serde_json::from_str(example)
+                                .unwrap_or_else(|_| Value::String(example.clone()))
+                        })
+                        .collect(),
+                ),
+            );
+        }
+        map
     }
 }
 
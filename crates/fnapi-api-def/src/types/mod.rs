@@ -25,6 +25,22 @@ impl From<Type> for TsType {
             Type::Keyword(t) => TsType::TsKeywordType(t.into()),
             Type::Array(t) => TsType::TsArrayType(t.into()),
             Type::Tuple(t) => TsType::TsTupleType(t.into()),
+            // `Date` is sent as a string by `toJSON`.
+            Type::Object(ObjectType {
+                builtin: Some(builtin),
+                ..
+            }) if builtin == "Date" => TsType::TsKeywordType(TsKeywordType {
+                span: DUMMY_SP,
+                kind: TsKeywordTypeKind::TsStringKeyword,
+            }),
+            Type::Object(ObjectType {
+                builtin: Some(builtin),
+                ..
+            }) => TsType::TsTypeRef(TsTypeRef {
+                span: DUMMY_SP,
+                type_name: TsEntityName::Ident(quote_ident!(DUMMY_SP, &**builtin)),
+                type_params: Default::default(),
+            }),
             Type::Object(t) => TsType::TsTypeLit(t.into()),
             Type::Intersection(t) => TsType::TsUnionOrIntersectionType(
                 TsUnionOrIntersectionType::TsIntersectionType(t.into()),
@@ -116,13 +132,60 @@ impl From<TupleType> for TsTupleType {
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct ObjectType {
     pub members: Vec<TypeElement>,
+
+    /// Index signature, like `[key: string]: Todo` of `Record<string, Todo>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_signature: Option<IndexSignature>,
+
+    /// Name of a builtin class like `Date`, whose instances are sent as-is.
+    /// Members of builtin classes are not recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub builtin: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct IndexSignature {
+    /// `string` or `number`.
+    pub key: TsKeywordTypeKind,
+    #[serde(rename = "type")]
+    pub ty: Box<Type>,
+}
+
+impl From<IndexSignature> for TsIndexSignature {
+    fn from(t: IndexSignature) -> Self {
+        TsIndexSignature {
+            span: DUMMY_SP,
+            params: vec![TsFnParam::Ident(BindingIdent {
+                id: quote_ident!("key"),
+                type_ann: Some(TsTypeAnn {
+                    span: DUMMY_SP,
+                    type_ann: box TsType::TsKeywordType(TsKeywordType {
+                        span: DUMMY_SP,
+                        kind: t.key,
+                    }),
+                }),
+            })],
+            type_ann: Some(t.ty.into()),
+            readonly: false,
+            is_static: false,
+        }
+    }
 }
 
 impl From<ObjectType> for TsTypeLit {
     fn from(t: ObjectType) -> Self {
         Self {
             span: DUMMY_SP,
-            members: t.members.into_iter().map(From::from).collect(),
+            members: t
+                .members
+                .into_iter()
+                .map(From::from)
+                .chain(
+                    t.index_signature
+                        .map(|sig| TsTypeElement::TsIndexSignature(sig.into())),
+                )
+                .collect(),
         }
     }
 }
@@ -141,13 +204,29 @@ impl From<TypeElement> for TsTypeElement {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Property {
     pub name: String,
     #[serde(rename = "type")]
     pub ty: Box<Type>,
     pub optional: bool,
+
+    /// From the JSDoc comment of the property.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Code of `@example` tags of the property.
+    #[serde(default)]
+    pub examples: Vec<String>,
+}
+
+/// Documentation is not a part of the type, so changing it does not change
+/// the api.
+impl PartialEq for Property {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.ty == other.ty && self.optional == other.optional
+    }
 }
 
 impl From<Property> for TsPropertySignature {
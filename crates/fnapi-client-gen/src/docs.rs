@@ -0,0 +1,98 @@
+//! JSDoc comments of generated clients.
+
+use fnapi_api_def::{types::Property, ApiFn};
+use swc_common::{
+    comments::{Comment, CommentKind},
+    DUMMY_SP,
+};
+
+use crate::destructured_arg;
+
+/// Creates the JSDoc comment of `f`, with descriptions of parameters.
+///
+/// `depth` is the indentation level of the method.
+pub(crate) fn fn_jsdoc(f: &ApiFn, depth: usize) -> Option<Comment> {
+    let mut tags = vec![];
+
+    for (idx, param) in f.params.iter().enumerate() {
+        if param.properties.is_empty() {
+            if let Some(desc) = &param.description {
+                tags.push(format!("@param {} - {}", param.key(idx), desc));
+            }
+        } else {
+            // `@param p0.title - Title of the todo`
+            let arg = destructured_arg(f, idx);
+            for (prop_idx, prop) in param.properties.iter().enumerate() {
+                if let Some(desc) = &prop.description {
+                    tags.push(format!(
+                        "@param {}.{} - {}",
+                        arg.sym,
+                        prop.key(prop_idx),
+                        desc
+                    ));
+                }
+            }
+        }
+    }
+
+    if let Some(reason) = &f.docs.deprecated {
+        tags.push(format!("@deprecated {}", reason).trim_end().to_string());
+    }
+    for example in &f.docs.examples {
+        tags.push(format!("@example\n{}", example));
+    }
+
+    jsdoc(f.docs.description.as_deref(), &tags, depth)
+}
+
+/// Creates the JSDoc comment of a property of an object type.
+pub(crate) fn property_jsdoc(p: &Property, depth: usize) -> Option<Comment> {
+    let tags = p
+        .examples
+        .iter()
+        .map(|example| format!("@example\n{}", example))
+        .collect::<Vec<_>>();
+
+    jsdoc(p.description.as_deref(), &tags, depth)
+}
+
+/// Creates `/** description */`, or a comment with a line for each line of
+/// the description and tags.
+fn jsdoc(description: Option<&str>, tags: &[String], depth: usize) -> Option<Comment> {
+    let text = match (description, tags) {
+        (None, []) => return None,
+        (Some(desc), []) if !desc.contains('\n') => format!("* {} ", desc),
+        _ => {
+            let indent = "    ".repeat(depth);
+            let mut lines = vec![];
+            if let Some(desc) = description {
+                lines.extend(desc.lines());
+                if !tags.is_empty() {
+                    lines.push("");
+                }
+            }
+            lines.extend(tags.iter().flat_map(|tag| tag.lines()));
+
+            let mut text = String::from("*");
+            for line in lines {
+                text.push('\n');
+                text.push_str(&indent);
+                text.push_str(" *");
+                if !line.is_empty() {
+                    text.push(' ');
+                    text.push_str(line);
+                }
+            }
+            text.push('\n');
+            text.push_str(&indent);
+            text.push(' ');
+            text
+        }
+    };
+
+    Some(Comment {
+        kind: CommentKind::Block,
+        span: DUMMY_SP,
+        text: text.into(),
+    })
+}
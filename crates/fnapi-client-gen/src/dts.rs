@@ -0,0 +1,225 @@
+use anyhow::Result;
+use fnapi_api_def::{
+    types::{Type, TypeElement},
+    ApiClass, ApiFn, ProjectApis,
+};
+use fnapi_core::Env;
+use swc_common::{comments::Comments, Span, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_utils::quote_ident;
+
+use crate::{
+    destructured_arg,
+    docs::{fn_jsdoc, property_jsdoc},
+};
+
+/// Generates TypeScript declarations for the clients generated by
+/// [JsClientConfig](crate::JsClientConfig).
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DtsClientConfig {}
+
+impl DtsClientConfig {
+    /// Generates the declarations. JSDoc comments of the api functions and
+    /// properties are added to `comments`.
+    pub fn generate(
+        &self,
+        env: &Env,
+        project: &ProjectApis,
+        comments: &dyn Comments,
+    ) -> Result<Module> {
+        env.with(|| {
+            let body = project
+                .classes()
+                .map(|class| self.generate_class(class, comments))
+                .map(ModuleDecl::ExportDecl)
+                .map(ModuleItem::ModuleDecl)
+                .collect();
+
+            Ok(Module {
+                span: DUMMY_SP,
+                body,
+                shebang: Default::default(),
+            })
+        })
+    }
+
+    /// Declares `export declare const ClassName: { ... };`
+    fn generate_class(&self, class: &ApiClass, comments: &dyn Comments) -> ExportDecl {
+        ExportDecl {
+            span: DUMMY_SP,
+            decl: Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Const,
+                declare: true,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(BindingIdent {
+                        id: Ident::new(class.class_name.clone(), DUMMY_SP),
+                        type_ann: Some(TsTypeAnn {
+                            span: DUMMY_SP,
+                            type_ann: box TsType::TsTypeLit(TsTypeLit {
+                                span: DUMMY_SP,
+                                members: class
+                                    .functions
+                                    .iter()
+                                    .map(|f| self.generate_fn(f, comments))
+                                    .collect(),
+                            }),
+                        }),
+                    }),
+                    init: None,
+                    definite: Default::default(),
+                }],
+            }),
+        }
+    }
+
+    fn generate_fn(&self, f: &ApiFn, comments: &dyn Comments) -> TsTypeElement {
+        let params = f
+            .params
+            .iter()
+            .enumerate()
+            .map(|(idx, param)| {
+                // Destructured parameters are named like `p0`.
+                let name = if param.properties.is_empty() {
+                    param.key(idx)
+                } else {
+                    destructured_arg(f, idx).sym.to_string()
+                };
+                let ty = ts_type(&param.ty, 1, comments);
+
+                // A required parameter can't follow an optional parameter, so
+                // such optional parameters accept `undefined` instead.
+                let is_trailing = f.params[idx..].iter().all(|param| param.optional);
+                let ty = if param.optional && !is_trailing {
+                    TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(
+                        TsUnionType {
+                            span: DUMMY_SP,
+                            types: vec![
+                                box ty,
+                                box TsType::TsKeywordType(TsKeywordType {
+                                    span: DUMMY_SP,
+                                    kind: TsKeywordTypeKind::TsUndefinedKeyword,
+                                }),
+                            ],
+                        },
+                    ))
+                } else {
+                    ty
+                };
+                let type_ann = Some(TsTypeAnn {
+                    span: DUMMY_SP,
+                    type_ann: box ty,
+                });
+
+                if param.rest {
+                    return TsFnParam::Rest(RestPat {
+                        span: DUMMY_SP,
+                        dot3_token: DUMMY_SP,
+                        arg: box Pat::Ident(Ident::new(name.into(), DUMMY_SP).into()),
+                        type_ann,
+                    });
+                }
+
+                TsFnParam::Ident(BindingIdent {
+                    id: Ident {
+                        optional: param.optional && is_trailing,
+                        ..Ident::new(name.into(), DUMMY_SP)
+                    },
+                    type_ann,
+                })
+            })
+            .collect();
+
+        // Clients always return a promise.
+        let return_type = TsType::TsTypeRef(TsTypeRef {
+            span: DUMMY_SP,
+            type_name: TsEntityName::Ident(quote_ident!("Promise")),
+            type_params: Some(TsTypeParamInstantiation {
+                span: DUMMY_SP,
+                params: vec![box ts_type(&f.return_type, 1, comments)],
+            }),
+        });
+
+        // Methods are declared at the first level of indentation.
+        let span = match fn_jsdoc(f, 1) {
+            Some(doc) => {
+                let span = Span::dummy_with_cmt();
+                comments.add_leading(span.lo, doc);
+                span
+            }
+            None => DUMMY_SP,
+        };
+
+        TsTypeElement::TsMethodSignature(TsMethodSignature {
+            span,
+            readonly: false,
+            key: box Expr::Ident(Ident::new(f.name.clone(), DUMMY_SP)),
+            computed: false,
+            optional: false,
+            params,
+            type_ann: Some(TsTypeAnn {
+                span: DUMMY_SP,
+                type_ann: box return_type,
+            }),
+            type_params: Default::default(),
+        })
+    }
+}
+
+/// Converts `ty`, which is printed at the indentation level `depth`, and adds
+/// JSDoc comments of its properties to `comments`.
+fn ts_type(ty: &Type, depth: usize, comments: &dyn Comments) -> TsType {
+    let mut ts_type = ty.clone().into();
+    add_property_docs(ty, &mut ts_type, depth, comments);
+    ts_type
+}
+
+fn add_property_docs(ty: &Type, ts_type: &mut TsType, depth: usize, comments: &dyn Comments) {
+    match (ty, ts_type) {
+        (Type::Object(ty), TsType::TsTypeLit(lit)) => {
+            for (TypeElement::Property(prop), member) in ty.members.iter().zip(&mut lit.members) {
+                let sig = match member {
+                    TsTypeElement::TsPropertySignature(sig) => sig,
+                    _ => continue,
+                };
+
+                // Properties are printed one level deeper than the braces.
+                if let Some(doc) = property_jsdoc(prop, depth + 1) {
+                    sig.span = Span::dummy_with_cmt();
+                    comments.add_leading(sig.span.lo, doc);
+                }
+                if let Some(ann) = &mut sig.type_ann {
+                    add_property_docs(&prop.ty, &mut ann.type_ann, depth + 1, comments);
+                }
+            }
+        }
+        (Type::Array(ty), TsType::TsArrayType(arr)) => {
+            add_property_docs(&ty.elem, &mut arr.elem_type, depth, comments);
+        }
+        (Type::Tuple(ty), TsType::TsTupleType(tuple)) => {
+            for (ty, elem) in ty.elems.iter().zip(&mut tuple.elem_types) {
+                add_property_docs(ty, &mut elem.ty, depth, comments);
+            }
+        }
+        (
+            Type::Union(ty),
+            TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(union)),
+        ) => {
+            for (ty, elem) in ty.types.iter().zip(&mut union.types) {
+                add_property_docs(ty, elem, depth, comments);
+            }
+        }
+        (
+            Type::Intersection(ty),
+            TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(
+                intersection,
+            )),
+        ) => {
+            for (ty, elem) in ty.types.iter().zip(&mut intersection.types) {
+                add_property_docs(ty, elem, depth, comments);
+            }
+        }
+        _ => {}
+    }
+}
@@ -6,13 +6,22 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use fnapi_api_def::{ApiFile, ApiFn, ProjectApis};
+use fnapi_api_def::{ApiClass, ApiFn, HttpMethod, ParamLocation, ProjectApis, Validation};
 use fnapi_core::Env;
 use rayon::prelude::*;
-use swc_common::DUMMY_SP;
+use swc_common::{
+    comments::{Comment, Comments},
+    BytePos, Span, DUMMY_SP,
+};
 use swc_ecma_ast::*;
 use swc_ecma_utils::{private_ident, quote_ident, ExprFactory};
 
+use self::docs::fn_jsdoc;
+pub use self::dts::DtsClientConfig;
+
+mod docs;
+mod dts;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum JsTargetEnv {
     Web,
@@ -22,10 +31,20 @@ pub enum JsTargetEnv {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct JsClientConfig {
     pub target_env: JsTargetEnv,
+
+    /// Prefix for the path of all routes, like `/api`.
+    pub route_prefix: Option<String>,
 }
 
 impl JsClientConfig {
-    pub fn generate(&self, env: &Env, project: &ProjectApis) -> Result<Module> {
+    /// Generates the client. JSDoc comments of the api functions are added to
+    /// `comments`.
+    pub fn generate(
+        &self,
+        env: &Env,
+        project: &ProjectApis,
+        comments: &dyn Comments,
+    ) -> Result<Module> {
         env.with(|| {
             let client = private_ident!("__client");
             let import = ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
@@ -46,16 +65,50 @@ impl JsClientConfig {
                 asserts: Default::default(),
             }));
 
+            // Clients run the validators of `@Validate` before sending requests.
+            let validators = private_ident!("__validators");
+            let has_validations = project.classes().any(|class| {
+                class
+                    .functions
+                    .iter()
+                    .any(|f| f.params.iter().any(|param| !param.validations.is_empty()))
+            });
+
             let mut body = project
                 .files
                 .par_iter()
+                .flat_map_iter(|file| file.classes.iter())
                 .map(|v| {
-                    self.generate_file(env, v, &client)
-                        .map(ModuleDecl::ExportDecl)
-                        .map(ModuleItem::ModuleDecl)
+                    self.generate_class(env, v, &client, &validators)
+                        .map(|(decl, docs)| {
+                            (ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(decl)), docs)
+                        })
                 })
-                .collect::<Result<Vec<_>>>()?;
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .map(|(item, docs)| {
+                    for (pos, doc) in docs {
+                        comments.add_leading(pos, doc);
+                    }
+                    item
+                })
+                .collect::<Vec<_>>();
 
+            if has_validations {
+                body.insert(
+                    0,
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                        span: DUMMY_SP,
+                        specifiers: vec![ImportSpecifier::Namespace(ImportStarAsSpecifier {
+                            span: DUMMY_SP,
+                            local: validators,
+                        })],
+                        src: "@fnapi/api/validators/index.js".into(),
+                        type_only: false,
+                        asserts: Default::default(),
+                    })),
+                );
+            }
             body.insert(0, import);
 
             Ok(Module {
@@ -66,8 +119,17 @@ impl JsClientConfig {
         })
     }
 
-    fn generate_file(&self, env: &Env, file: &Arc<ApiFile>, client: &Ident) -> Result<ExportDecl> {
-        Ok(ExportDecl {
+    /// Returns the declaration, with JSDoc comments to add.
+    fn generate_class(
+        &self,
+        env: &Env,
+        class: &Arc<ApiClass>,
+        client: &Ident,
+        validators: &Ident,
+    ) -> Result<(ExportDecl, Vec<(BytePos, Comment)>)> {
+        let (object, docs) = self.generate_object_for_class(env, class, client, validators)?;
+
+        let decl = ExportDecl {
             span: DUMMY_SP,
             decl: Decl::Var(VarDecl {
                 span: DUMMY_SP,
@@ -75,32 +137,44 @@ impl JsClientConfig {
                 declare: Default::default(),
                 decls: vec![VarDeclarator {
                     span: DUMMY_SP,
-                    name: Ident::new(file.class_name.clone(), DUMMY_SP).into(),
-                    init: Some(box Expr::Object(
-                        self.generate_object_for_file(env, file, client)?,
-                    )),
+                    name: Ident::new(class.class_name.clone(), DUMMY_SP).into(),
+                    init: Some(box Expr::Object(object)),
                     definite: Default::default(),
                 }],
             }),
-        })
+        };
+        Ok((decl, docs))
     }
 
-    fn generate_object_for_file(
+    fn generate_object_for_class(
         &self,
         env: &Env,
-        file: &Arc<ApiFile>,
+        class: &Arc<ApiClass>,
         client: &Ident,
-    ) -> Result<ObjectLit> {
+        validators: &Ident,
+    ) -> Result<(ObjectLit, Vec<(BytePos, Comment)>)> {
         env.with(|| {
-            let fns = file
+            let mut docs = vec![];
+            let fns = class
                 .functions
                 .iter()
                 .map(|f| {
-                    self.generate_fn(file, f, client)
-                        .map(|f| {
+                    // Methods are declared at the first level of indentation.
+                    let doc = fn_jsdoc(f, 1);
+
+                    self.generate_fn(class, f, client, validators)
+                        .map(|decl| {
+                            // Comments of methods are attached to the span of
+                            // the function.
+                            let mut function = decl.function;
+                            if let Some(doc) = doc {
+                                function.span = Span::dummy_with_cmt();
+                                docs.push((function.span.lo, doc));
+                            }
+
                             Prop::Method(MethodProp {
-                                key: f.ident.clone().into(),
-                                function: f.function,
+                                key: decl.ident.into(),
+                                function,
                             })
                         })
                         .map(Box::new)
@@ -108,15 +182,167 @@ impl JsClientConfig {
                 })
                 .collect::<Result<Vec<_>>>()?;
 
-            Ok(ObjectLit {
-                span: DUMMY_SP,
-                props: fns,
-            })
+            Ok((
+                ObjectLit {
+                    span: DUMMY_SP,
+                    props: fns,
+                },
+                docs,
+            ))
         })
     }
 
-    fn generate_fn(&self, file: &ApiFile, f: &Arc<ApiFn>, client: &Ident) -> Result<FnDecl> {
-        let stmt = Stmt::Return(ReturnStmt {
+    fn generate_fn(
+        &self,
+        class: &ApiClass,
+        f: &Arc<ApiFn>,
+        client: &Ident,
+        validators: &Ident,
+    ) -> Result<FnDecl> {
+        let mut fn_params = vec![];
+        // Parameters sent in requests, with the keys and the values.
+        let mut sent = vec![];
+        for (idx, param) in f.params.iter().enumerate() {
+            if param.properties.is_empty() {
+                let ident = quote_ident!(&*param.key(idx));
+
+                fn_params.push(if param.rest {
+                    Pat::Rest(RestPat {
+                        span: DUMMY_SP,
+                        dot3_token: DUMMY_SP,
+                        arg: box Pat::Ident(ident.clone().into()),
+                        type_ann: Default::default(),
+                    })
+                } else {
+                    Pat::Ident(ident.clone().into())
+                });
+                sent.push((ident.clone(), Expr::Ident(ident), param));
+                continue;
+            }
+
+            // Properties of destructured parameters are read from the
+            // argument, like `p0?.title`.
+            let arg = destructured_arg(f, idx);
+            fn_params.push(Pat::Ident(arg.clone().into()));
+            for (prop_idx, prop) in param.properties.iter().enumerate() {
+                let key = quote_ident!(&*prop.key(prop_idx));
+                let value = Expr::OptChain(OptChainExpr {
+                    span: DUMMY_SP,
+                    question_dot_token: DUMMY_SP,
+                    base: OptChainBase::Member(MemberExpr {
+                        span: DUMMY_SP,
+                        obj: box arg.clone().into(),
+                        prop: MemberProp::Ident(key.clone()),
+                    }),
+                });
+                sent.push((key, value, prop));
+            }
+        }
+
+        // Parameters are sent as an object, like `{ email: email }`.
+        let mut args = vec![
+            class.class_name.clone().as_arg(),
+            f.name.clone().as_arg(),
+            ObjectLit {
+                span: DUMMY_SP,
+                props: sent
+                    .iter()
+                    .map(|(key, value, _)| {
+                        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                            key: key.clone().into(),
+                            value: box value.clone(),
+                        }))
+                    })
+                    .collect(),
+            }
+            .as_arg(),
+        ];
+
+        let mut options = vec![];
+        if let Some(prefix) = &self.route_prefix {
+            options.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: quote_ident!("prefix").into(),
+                value: box Expr::from(&**prefix),
+            })));
+        }
+        // The client uses `POST /{ClassName}/{name}` by default.
+        if f.method != HttpMethod::Post {
+            options.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: quote_ident!("method").into(),
+                value: box Expr::from(f.method.as_str()),
+            })));
+        }
+        if let Some(path) = &f.path {
+            options.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: quote_ident!("path").into(),
+                value: box Expr::from(&**path),
+            })));
+        }
+        // The client sends each parameter in the body by default.
+        if sent
+            .iter()
+            .any(|(_, _, param)| param.location != ParamLocation::Body)
+        {
+            options.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: quote_ident!("parameterLocations").into(),
+                value: box Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: sent
+                        .iter()
+                        .filter(|(_, _, param)| param.location != ParamLocation::Body)
+                        .map(|(key, _, param)| {
+                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                key: key.clone().into(),
+                                value: box Expr::from(param.location.as_str()),
+                            }))
+                        })
+                        .collect(),
+                }),
+            })));
+        }
+        if !options.is_empty() {
+            args.push(
+                ObjectLit {
+                    span: DUMMY_SP,
+                    props: options,
+                }
+                .as_arg(),
+            );
+        }
+
+        // `__validators.check("email", email, [__validators.email])`
+        let mut stmts = sent
+            .iter()
+            .filter(|(_, _, param)| !param.validations.is_empty())
+            .map(|(key, value, param)| {
+                Stmt::Expr(ExprStmt {
+                    span: DUMMY_SP,
+                    expr: box Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: validators
+                            .clone()
+                            .make_member(quote_ident!("check"))
+                            .as_callee(),
+                        args: vec![
+                            key.sym.clone().as_arg(),
+                            value.clone().as_arg(),
+                            ArrayLit {
+                                span: DUMMY_SP,
+                                elems: param
+                                    .validations
+                                    .iter()
+                                    .map(|v| Some(validator_expr(validators, v).as_arg()))
+                                    .collect(),
+                            }
+                            .as_arg(),
+                        ],
+                        type_args: Default::default(),
+                    }),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        stmts.push(Stmt::Return(ReturnStmt {
             span: DUMMY_SP,
             arg: Some(box Expr::Call(CallExpr {
                 span: DUMMY_SP,
@@ -124,24 +350,27 @@ impl JsClientConfig {
                     .clone()
                     .make_member(quote_ident!("invoke"))
                     .as_callee(),
-                args: vec![
-                    file.class_name.clone().as_arg(),
-                    f.name.clone().as_arg(),
-                    quote_ident!("arguments").as_arg(),
-                ],
+                args,
                 type_args: Default::default(),
             })),
-        });
+        }));
         Ok(FnDecl {
             ident: Ident::new(f.name.clone(), DUMMY_SP),
             declare: Default::default(),
             function: Function {
-                params: vec![],
+                params: fn_params
+                    .into_iter()
+                    .map(|pat| Param {
+                        span: DUMMY_SP,
+                        decorators: Default::default(),
+                        pat,
+                    })
+                    .collect(),
                 decorators: Default::default(),
                 span: DUMMY_SP,
                 body: Some(BlockStmt {
                     span: DUMMY_SP,
-                    stmts: vec![stmt],
+                    stmts,
                 }),
                 is_generator: false,
                 is_async: true,
@@ -151,3 +380,39 @@ impl JsClientConfig {
         })
     }
 }
+
+/// Returns the argument for the destructured parameter at `idx` of `f`, like
+/// `p0`. Underscores are prepended while it clashes with the name of another
+/// parameter, like `(p1: string, { title }: Todo)`.
+fn destructured_arg(f: &ApiFn, idx: usize) -> Ident {
+    let mut name = format!("p{}", idx);
+    while f
+        .params
+        .iter()
+        .any(|param| param.name.as_deref() == Some(&*name))
+    {
+        name.insert(0, '_');
+    }
+    quote_ident!(name)
+}
+
+/// Creates an expression for `v`, like `__validators.minLength(8)`.
+fn validator_expr(validators: &Ident, v: &Validation) -> Expr {
+    let (name, length) = match v {
+        Validation::Required => ("required", None),
+        Validation::Email => ("email", None),
+        Validation::MinLength { length } => ("minLength", Some(*length)),
+        Validation::MaxLength { length } => ("maxLength", Some(*length)),
+    };
+
+    let validator = validators.clone().make_member(quote_ident!(name));
+    match length {
+        Some(length) => Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: validator.as_callee(),
+            args: vec![(length as f64).as_arg()],
+            type_args: Default::default(),
+        }),
+        None => validator,
+    }
+}
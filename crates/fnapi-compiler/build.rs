@@ -1,9 +1,17 @@
-use std::{env, path::PathBuf, process::Command};
+use std::{
+    collections::hash_map::DefaultHasher,
+    env, fs,
+    hash::Hasher,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=rollup.config.js");
     println!("cargo:rerun-if-changed=src/**/*.ts");
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=../fnapi-api-def/src");
 
     let out_dir = env::var("OUT_DIR").map(PathBuf::from).unwrap();
     let out_dir = out_dir
@@ -38,4 +46,34 @@ fn main() {
 
     let status = c.arg("rollup").arg("-c").status().unwrap();
     assert!(status.success(), "rollup failed");
+
+    // Outputs of the compiler depend on its sources, the type server and the
+    // api definitions, so caches are keyed by their hash.
+    let mut hasher = DefaultHasher::new();
+    hash_dir(&mut hasher, Path::new("src"));
+    hash_dir(&mut hasher, Path::new("../fnapi-api-def/src"));
+    hasher.write(&fs::read(out_dir.join("type-server.js")).expect("failed to read type server"));
+    println!(
+        "cargo:rustc-env=FNAPI_COMPILER_BUILD_ID={}-{:016x}",
+        env::var("CARGO_PKG_VERSION").unwrap(),
+        hasher.finish()
+    );
+}
+
+/// Hashes paths and contents of all files in `dir`, in a stable order.
+fn hash_dir(hasher: &mut DefaultHasher, dir: &Path) {
+    let mut entries = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("failed to read `{}`: {}", dir.display(), err))
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            hash_dir(hasher, &path);
+        } else {
+            hasher.write(path.to_string_lossy().as_bytes());
+            hasher.write(&fs::read(&path).unwrap());
+        }
+    }
 }
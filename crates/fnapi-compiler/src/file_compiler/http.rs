@@ -0,0 +1,303 @@
+//! Static analysis of `@Http`.
+
+use fnapi_api_def::{HttpMethod, ParamLocation};
+use swc_atoms::JsWord;
+use swc_common::{errors::HANDLER, Span, Spanned};
+use swc_ecmascript::ast::*;
+
+use super::params::{is_optional_param, param_name};
+
+/// Route selected by `@Http`.
+#[derive(Debug)]
+pub(super) struct HttpRoute {
+    pub method: HttpMethod,
+    /// Path template, like `/todos/:id`.
+    pub path: Option<String>,
+}
+
+/// Parses `@Http({ method: "GET", path: "/todos/:id" })`, and binds path
+/// parameters to `params` by name.
+///
+/// Returns [None] if there's an error.
+pub(super) fn parse_http_route(
+    span: Span,
+    args: &[ExprOrSpread],
+    params: &[Param],
+    locations: &mut [ParamLocation],
+) -> Option<HttpRoute> {
+    let obj = match args {
+        [] => None,
+        [ExprOrSpread {
+            spread: None,
+            expr: box Expr::Object(obj),
+        }] => Some(obj),
+        _ => {
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_err(span, "@Http takes an object literal")
+                    .help("Use it like '@Http({ method: \"GET\", path: \"/todos/:id\" })'")
+                    .emit();
+            });
+            return None;
+        }
+    };
+
+    let mut method = None;
+    let mut path = None;
+    let mut has_error = false;
+
+    for prop in obj.iter().flat_map(|obj| obj.props.iter()) {
+        let (key, value) = match prop {
+            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp { key, value })) => {
+                match prop_name(key) {
+                    Some(name) => (name, value),
+                    None => {
+                        emit(key.span(), "Keys of @Http options should be identifiers");
+                        has_error = true;
+                        continue;
+                    }
+                }
+            }
+            _ => {
+                emit(
+                    prop.span(),
+                    "@Http options should be written like 'method: \"GET\"'",
+                );
+                has_error = true;
+                continue;
+            }
+        };
+
+        let value = match &**value {
+            Expr::Lit(Lit::Str(s)) => s,
+            _ => {
+                emit(value.span(), "@Http options should be string literals");
+                has_error = true;
+                continue;
+            }
+        };
+
+        match &*key {
+            "method" => match HttpMethod::parse(&value.value) {
+                Some(v) => method = Some(v),
+                None => {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_err(
+                                value.span,
+                                &format!("Unsupported HTTP method '{}'", value.value),
+                            )
+                            .help(&format!(
+                                "Use one of {}",
+                                HttpMethod::ALL.map(|m| m.as_str()).join(", ")
+                            ))
+                            .emit();
+                    });
+                    has_error = true;
+                }
+            },
+            "path" => match bind_path_params(value, params, locations) {
+                Some(v) => path = Some(v),
+                None => has_error = true,
+            },
+            _ => {
+                emit(prop.span(), &format!("Unknown option '{}' of @Http", key));
+                has_error = true;
+            }
+        }
+    }
+
+    if has_error {
+        return None;
+    }
+
+    Some(HttpRoute {
+        method: method.unwrap_or_default(),
+        path,
+    })
+}
+
+/// Reports parameters in the body of a GET request, as Fastify does not
+/// support bodies of GET requests.
+///
+/// `selected_by` is the span of `@Http` or `FnApi`, which selects the method,
+/// and `decorator` is its name.
+///
+/// Returns [None] if there's an error.
+pub(super) fn check_body_of_get(
+    method: HttpMethod,
+    selected_by: Span,
+    decorator: &str,
+    params: &[Param],
+    locations: &[ParamLocation],
+) -> Option<()> {
+    if method != HttpMethod::Get {
+        return Some(());
+    }
+
+    let body_param = params
+        .iter()
+        .zip(locations.iter())
+        .find(|(_, location)| **location == ParamLocation::Body);
+
+    if let Some((param, _)) = body_param {
+        HANDLER.with(|handler| {
+            handler
+                .struct_span_err(
+                    param.span,
+                    "Parameters of GET requests can't be in the body",
+                )
+                .span_note(
+                    selected_by,
+                    &format!("The method is selected by this {}", decorator),
+                )
+                .help("Use @Query() or @Path()")
+                .emit();
+        });
+        return None;
+    }
+
+    Some(())
+}
+
+/// Validates the path template `path`, and marks each bound parameter as a
+/// path parameter.
+fn bind_path_params(
+    path: &Str,
+    params: &[Param],
+    locations: &mut [ParamLocation],
+) -> Option<String> {
+    if !path.value.starts_with('/') {
+        emit(path.span, "Path of @Http should start with '/'");
+        return None;
+    }
+
+    let mut bound = vec![false; params.len()];
+    let mut segments = vec![];
+
+    for segment in path.value.split('/').skip(1) {
+        let name = match segment.strip_prefix(':') {
+            Some(name) => name,
+            None => {
+                if segment.contains(':') || segment.contains('*') {
+                    emit(
+                        path.span,
+                        &format!("Invalid segment '{}' in the path of @Http", segment),
+                    );
+                    return None;
+                }
+                segments.push(segment.to_string());
+                continue;
+            }
+        };
+
+        let idx = params
+            .iter()
+            .position(|param| param_name(param).map_or(false, |i| *i.sym == *name));
+        let idx = match idx {
+            Some(idx) => idx,
+            None => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(
+                            path.span,
+                            &format!("Path parameter ':{}' does not match any parameter", name),
+                        )
+                        .help("Path parameters are bound to parameters with the same name")
+                        .emit();
+                });
+                return None;
+            }
+        };
+
+        if bound[idx] {
+            emit(
+                path.span,
+                &format!("Path parameter ':{}' is used more than once", name),
+            );
+            return None;
+        }
+        if !matches!(locations[idx], ParamLocation::Body | ParamLocation::Path) {
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_err(
+                        path.span,
+                        &format!(
+                            "Path parameter ':{}' is bound to a parameter not in the path",
+                            name
+                        ),
+                    )
+                    .span_note(
+                        params[idx].span,
+                        &format!("This parameter is in the {}", locations[idx].as_str()),
+                    )
+                    .emit();
+            });
+            return None;
+        }
+
+        if is_optional_param(&params[idx]) {
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_err(
+                        params[idx].span,
+                        "Parameters bound to the path can't be optional",
+                    )
+                    .span_note(path.span, "Path of the route")
+                    .emit();
+            });
+            return None;
+        }
+
+        bound[idx] = true;
+        locations[idx] = ParamLocation::Path;
+        segments.push(format!(":{}", name));
+    }
+
+    for (idx, param) in params.iter().enumerate() {
+        if locations[idx] == ParamLocation::Path && !bound[idx] {
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_err(param.span, "@Path() parameter is not in the path of @Http")
+                    .span_note(path.span, "Path of the route")
+                    .emit();
+            });
+            return None;
+        }
+    }
+
+    Some(format!("/{}", segments.join("/")))
+}
+
+/// Returns the static `httpMethod` of the options object of `@FnApi`.
+pub(super) fn http_method_of_fn_api(args: &[ExprOrSpread]) -> Option<HttpMethod> {
+    let obj = match args.first()? {
+        ExprOrSpread {
+            spread: None,
+            expr: box Expr::Object(obj),
+        } => obj,
+        _ => return None,
+    };
+
+    obj.props.iter().find_map(|prop| match prop {
+        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+            key,
+            value: box Expr::Lit(Lit::Str(value)),
+        })) if prop_name(key).as_deref() == Some("httpMethod") => HttpMethod::parse(&value.value),
+        _ => None,
+    })
+}
+
+fn prop_name(key: &PropName) -> Option<JsWord> {
+    match key {
+        PropName::Ident(i) => Some(i.sym.clone()),
+        PropName::Str(s) => Some(s.value.clone()),
+        _ => None,
+    }
+}
+
+fn emit(span: Span, msg: &str) {
+    HANDLER.with(|handler| {
+        handler.struct_span_err(span, msg).emit();
+    });
+}
@@ -41,6 +41,15 @@ impl ImportMap {
         }
     }
 
+    /// Returns true if `orig_name` of `module` is imported by name, or if
+    /// `module` is imported as a namespace.
+    pub fn has_import(&self, module: &str, orig_name: &str) -> bool {
+        self.imports
+            .values()
+            .any(|(src, sym)| src == module && sym == orig_name)
+            || self.namespace_imports.values().any(|src| src == module)
+    }
+
     pub fn analyze(m: &Module) -> Self {
         let mut data = ImportMap {
             imports: Default::default(),
@@ -0,0 +1,135 @@
+//! Documentation of api functions, from JSDoc comments.
+
+use fnapi_api_def::Docs;
+use swc_common::comments::{Comment, CommentKind};
+
+/// Parsed JSDoc comment of an api function.
+#[derive(Debug, Default)]
+pub(super) struct JsDoc {
+    pub docs: Docs,
+    /// `@param` tags, as `(name, description)`.
+    ///
+    /// Names of properties of destructured parameters are like `p.title`.
+    params: Vec<(String, String)>,
+}
+
+impl JsDoc {
+    /// Returns the description of the parameter or the property of a
+    /// destructured parameter named `name`.
+    pub fn param(&self, name: &str, is_property: bool) -> Option<String> {
+        self.params
+            .iter()
+            .find(|(tag_name, _)| {
+                if is_property {
+                    tag_name
+                        .split_once('.')
+                        .map_or(false, |(_, prop)| prop == name)
+                } else {
+                    tag_name == name
+                }
+            })
+            .map(|(_, desc)| desc.clone())
+    }
+}
+
+/// Returns true if `c` is a JSDoc comment, like `/** Logs in. */`.
+pub(super) fn is_jsdoc(c: &Comment) -> bool {
+    c.kind == CommentKind::Block && c.text.starts_with('*')
+}
+
+/// Parses the text of a JSDoc comment, without `/*` and `*/`.
+pub(super) fn parse_jsdoc(text: &str) -> JsDoc {
+    // Lines without the leading `*`.
+    let lines = text.strip_prefix('*').unwrap_or(text).lines().map(|line| {
+        let line = line.trim_start();
+        let line = line.strip_prefix('*').unwrap_or(line);
+        line.strip_prefix(' ').unwrap_or(line).trim_end()
+    });
+
+    let mut description = vec![];
+    // `(tag, text)`, where `text` contains the following lines.
+    let mut tags: Vec<(&str, String)> = vec![];
+
+    for line in lines {
+        if let Some(tag) = line.strip_prefix('@') {
+            let (name, rest) = tag.split_once(' ').unwrap_or((tag, ""));
+            tags.push((name, rest.to_string()));
+        } else if let Some((_, text)) = tags.last_mut() {
+            text.push('\n');
+            text.push_str(line);
+        } else {
+            description.push(line);
+        }
+    }
+
+    let mut doc = JsDoc {
+        docs: Docs {
+            description: non_empty(&description.join("\n")),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    for (name, text) in tags {
+        match name {
+            "param" => {
+                if let Some(param) = parse_param_tag(&text) {
+                    doc.params.push(param);
+                }
+            }
+            "deprecated" => {
+                doc.docs.deprecated = Some(text.trim().to_string());
+            }
+            "example" => {
+                if let Some(code) = non_empty(&text) {
+                    doc.docs.examples.push(code);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    doc
+}
+
+/// Parses `{string} title - The title`, `[limit=10] Max count` or
+/// `title The title`.
+fn parse_param_tag(text: &str) -> Option<(String, String)> {
+    let mut text = text.trim_start();
+
+    // Skip the type, which may contain braces.
+    if text.starts_with('{') {
+        let mut depth = 0;
+        let end = text.char_indices().find_map(|(idx, c)| {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => {}
+            }
+            (depth == 0).then_some(idx + 1)
+        })?;
+        text = text[end..].trim_start();
+    }
+
+    let (name, rest) = if let Some(optional) = text.strip_prefix('[') {
+        let (name, rest) = optional.split_once(']')?;
+        // `[limit=10]`
+        (name.split('=').next().unwrap_or(name).trim(), rest)
+    } else {
+        text.split_once(char::is_whitespace).unwrap_or((text, ""))
+    };
+
+    let rest = rest.trim_start();
+    let desc = rest.strip_prefix('-').unwrap_or(rest);
+
+    Some((name.to_string(), non_empty(desc)?))
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    let s = s.trim();
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.to_string())
+    }
+}
@@ -1,10 +1,24 @@
-use std::sync::Arc;
+use std::{
+    fs::read_to_string,
+    iter::once,
+    mem::{replace, take},
+    path::Path,
+    sync::Arc,
+};
 
-use anyhow::Result;
-use fnapi_api_def::{types::json_schema::ToJsonSchema, ApiFile, ApiFn, ApiParam};
+use anyhow::{Context, Result};
+use fnapi_api_def::{
+    types::json_schema::ToJsonSchema, ApiClass, ApiFile, ApiFn, ApiParam, ParamLocation,
+};
 use fnapi_core::Env;
+use module_storage::modules::LeadingComments;
 use swc_atoms::JsWord;
-use swc_common::{errors::HANDLER, util::take::Take, Mark, Span, Spanned, SyntaxContext, DUMMY_SP};
+use swc_common::{
+    comments::{Comment, SingleThreadedComments},
+    errors::HANDLER,
+    util::take::Take,
+    BytePos, SourceMap, Span, Spanned, SyntaxContext, DUMMY_SP,
+};
 use swc_ecma_transforms_base::{
     helpers::{inject_helpers, Helpers, HELPERS},
     resolver,
@@ -12,20 +26,96 @@ use swc_ecma_transforms_base::{
 use swc_ecma_transforms_proposal::decorators;
 use swc_ecmascript::{
     ast::*,
+    parser::{parse_file_as_module, Syntax, TsConfig},
     utils::{prepend_stmts, private_ident, quote_ident, ExprFactory},
-    visit::{FoldWith, VisitMut, VisitMutWith},
+    visit::{FoldWith, Visit, VisitMut, VisitMutWith, VisitWith},
 };
 use tokio::task::spawn_blocking;
 
-use self::{import_analyzer::ImportMap, magic_replacer::magic_replacer};
+use self::{
+    http::{check_body_of_get, http_method_of_fn_api, parse_http_route, HttpRoute},
+    import_analyzer::ImportMap,
+    jsdoc::{is_jsdoc, parse_jsdoc, JsDoc},
+    magic_replacer::magic_replacer,
+    params::{
+        destructured_prop_types, is_optional_param, param_init, param_name, param_shape, ParamShape,
+    },
+    response::response_projector,
+    validate::parse_validations,
+};
 use crate::{project::Project, target::ServerTarget, ServerApiFile};
 
+mod http;
 mod import_analyzer;
+mod jsdoc;
 mod magic_replacer;
+mod params;
+mod response;
+mod validate;
 
 const FNAPI_API_MODULE: &str = "@fnapi/api";
 
+/// Keys of `MethodDescriptor` for the schemas of parameters which are not in
+/// the body. Body parameters are described by `parameterTypes`.
+const LOCATION_SCHEMAS: &[(&str, ParamLocation)] = &[
+    ("queryStringJsonSchema", ParamLocation::Query),
+    ("paramsJsonSchema", ParamLocation::Path),
+    ("headersJsonSchema", ParamLocation::Header),
+];
+
+/// Parameter decorators exported by `@fnapi/api`, and the location selected by
+/// them.
+const PARAM_DECORATORS: &[(&str, ParamLocation)] = &[
+    ("Query", ParamLocation::Query),
+    ("Path", ParamLocation::Path),
+    ("Header", ParamLocation::Header),
+    ("Body", ParamLocation::Body),
+];
+
 impl ServerApiFile {
+    /// Returns true if `path` imports `FnApi` from `@fnapi/api`, or has an
+    /// `@fnapi` JSDoc tag.
+    ///
+    /// Files which fail to parse are treated as api files, so the parse errors
+    /// are reported by the compilation.
+    pub fn is_api_file(path: &Path) -> Result<bool> {
+        let src = read_to_string(path)
+            .with_context(|| format!("failed to load file `{}`", path.display()))?;
+
+        Ok(Self::is_api_source(path, src))
+    }
+
+    /// Same as [ServerApiFile::is_api_file], but uses `src` as the content of
+    /// `path`.
+    pub fn is_api_source(path: &Path, src: String) -> bool {
+        let cm = SourceMap::default();
+        let fm = cm.new_source_file(swc_common::FileName::Real(path.to_path_buf()), src);
+
+        let comments = SingleThreadedComments::default();
+        let m = match parse_file_as_module(
+            &fm,
+            Syntax::Typescript(TsConfig {
+                tsx: path.to_string_lossy().ends_with(".tsx"),
+                decorators: true,
+                ..Default::default()
+            }),
+            EsVersion::latest(),
+            Some(&comments),
+            &mut vec![],
+        ) {
+            Ok(m) => m,
+            Err(_) => return true,
+        };
+
+        if ImportMap::analyze(&m).has_import(FNAPI_API_MODULE, "FnApi") {
+            return true;
+        }
+
+        let (leading, _) = comments.take_all();
+        let has_tag = leading.borrow().values().flatten().any(is_fnapi_jsdoc);
+        has_tag
+    }
+
     pub async fn process(
         &self,
         env: &Env,
@@ -34,7 +124,7 @@ impl ServerApiFile {
         let name = Arc::new(swc_common::FileName::Real(self.path.clone()));
         let filename = self.path.display().to_string();
 
-        let m = env.with(|| project.modules.load(&self.path))?;
+        let (m, comments) = env.with(|| project.modules.load_with_comments(&self.path))?;
         let mut m = (*m).clone();
 
         let env = env.clone();
@@ -51,29 +141,28 @@ impl ServerApiFile {
                     let mut stmts_to_prepend = vec![];
                     let mut extras = vec![];
 
-                    let class_name;
-                    let methods;
+                    let classes;
                     {
                         let mut compiler = FileCompiler {
                             project: &project,
                             filename: &filename,
                             imports,
-                            top_level_mark,
-
-                            in_export_default_decl: false,
+                            comments,
 
                             wrap_fnapi_config: Default::default(),
+                            wrap_api_class: Default::default(),
 
                             stmts_to_prepend: &mut stmts_to_prepend,
                             stmts_to_append: &mut extras,
                             class_name: Default::default(),
                             compiled_method_records: Default::default(),
+                            classes: Default::default(),
+                            api_fns: Default::default(),
 
                             target: project.server_target.clone(),
                         };
                         m.visit_mut_with(&mut compiler);
-                        class_name = compiler.class_name;
-                        methods = compiler.compiled_method_records;
+                        classes = compiler.classes;
                     }
 
                     m = m.fold_with(&mut decorators::decorators(decorators::Config {
@@ -99,13 +188,7 @@ impl ServerApiFile {
                     m.visit_mut_with(&mut swc_ecma_transforms_base::hygiene::hygiene());
                     m.visit_mut_with(&mut swc_ecma_transforms_base::fixer::fixer(None));
 
-                    Ok((
-                        m,
-                        Arc::new(ApiFile {
-                            class_name,
-                            functions: methods.iter().map(|v| v.api_def.clone()).collect(),
-                        }),
-                    ))
+                    Ok((m, Arc::new(ApiFile { classes })))
                 })
             })
         })
@@ -118,30 +201,49 @@ struct FileCompiler<'a> {
     filename: &'a str,
 
     imports: Arc<ImportMap>,
-    top_level_mark: Mark,
-
-    in_export_default_decl: bool,
+    /// Used to find `@fnapi` JSDoc tags.
+    comments: Arc<LeadingComments>,
 
     wrap_fnapi_config: Option<Ident>,
+    wrap_api_class: Option<Ident>,
 
     stmts_to_prepend: &'a mut Vec<ModuleItem>,
     stmts_to_append: &'a mut Vec<ModuleItem>,
 
-    class_name: JsWord,
+    /// Name of the exported class being compiled.
+    class_name: Option<JsWord>,
+    /// Api methods of the class being compiled, or exported api functions if
+    /// not in a class.
     compiled_method_records: Vec<MethodRecord>,
+    classes: Vec<Arc<ApiClass>>,
+    /// Local names of exported api functions.
+    api_fns: Vec<Ident>,
 
     target: Arc<dyn ServerTarget>,
 }
 
+/// Decorators of an api function, which are removed by the compiler.
+#[derive(Debug, Default)]
+struct ApiDecorators {
+    /// Arguments of `@FnApi`, or leading arguments of `FnApi(fn)`.
+    args_to_fn_api: Option<Vec<ExprOrSpread>>,
+    /// Span and arguments of `@Http`.
+    http: Option<(Span, Vec<ExprOrSpread>)>,
+    /// Span and arguments of each `@Validate`.
+    validate: Vec<(Span, Vec<ExprOrSpread>)>,
+}
+
 #[derive(Debug, Clone)]
 struct MethodRecord {
     pub name: JsWord,
     pub config_object_var_name: Ident,
+    /// `true` if the route is selected by `@Http`.
+    pub has_http: bool,
     pub api_def: Arc<ApiFn>,
 }
 
 impl FileCompiler<'_> {
-    fn compile_api_method(&mut self, method: &mut ClassMethod) -> Option<!> {
+    fn compile_api_method(&mut self, class_name: &JsWord, method: &mut ClassMethod) -> Option<!> {
         let api_ann_span = get_span_of_api_decorator(&self.imports, &method.function.decorators)?;
 
         let name = match &method.key {
@@ -157,9 +259,16 @@ impl FileCompiler<'_> {
             }
         };
 
-        let mut args_to_fn_api = None;
+        // Comments of decorated methods are attached to the first decorator.
+        let doc_pos = method
+            .function
+            .decorators
+            .first()
+            .map_or(method.span.lo, |dec| dec.span.lo.min(method.span.lo));
+
+        let mut decorators = ApiDecorators::default();
         {
-            // Remove `@FnApi`
+            // Remove `@FnApi`, `@Http` and `@Validate`
             method
                 .function
                 .decorators
@@ -170,9 +279,15 @@ impl FileCompiler<'_> {
                         ..
                     }) => {
                         if self.imports.is_import(callee, FNAPI_API_MODULE, "FnApi") {
-                            assert_eq!(args_to_fn_api, None, "Multiple @FnApi?");
+                            assert_eq!(decorators.args_to_fn_api, None, "Multiple @FnApi?");
 
-                            args_to_fn_api = Some(args.take());
+                            decorators.args_to_fn_api = Some(args.take());
+                            false
+                        } else if self.imports.is_import(callee, FNAPI_API_MODULE, "Http") {
+                            decorators.http = Some((dec.span, args.take()));
+                            false
+                        } else if self.imports.is_import(callee, FNAPI_API_MODULE, "Validate") {
+                            decorators.validate.push((dec.span, args.take()));
                             false
                         } else {
                             true
@@ -182,30 +297,64 @@ impl FileCompiler<'_> {
                 });
         }
 
-        let config_object_name = Ident::new(
-            format!("__fnapi_config_for_{}", name.sym).into(),
+        self.compile_api_fn(
+            Some(class_name),
+            &name,
+            api_ann_span,
+            doc_pos,
+            decorators,
+            &mut method.function,
+        )
+    }
+
+    /// Compiles `function` to an api handler, and records it to
+    /// `compiled_method_records`.
+    ///
+    /// `class_name` is [None] for exported functions. Documentation is read
+    /// from the JSDoc comment at `doc_pos`.
+    fn compile_api_fn(
+        &mut self,
+        class_name: Option<&JsWord>,
+        name: &Ident,
+        api_ann_span: Span,
+        doc_pos: BytePos,
+        mut decorators: ApiDecorators,
+        function: &mut Function,
+    ) -> Option<!> {
+        let default_method = decorators
+            .args_to_fn_api
+            .as_deref()
+            .and_then(http_method_of_fn_api)
+            .unwrap_or_default();
+
+        // Methods of different classes may have the same name, so the config
+        // is renamed by the hygiene pass on conflicts.
+        let config_object_name = private_ident!(
             name.span.with_ctxt(SyntaxContext::empty()),
+            format!("__fnapi_config_for_{}", name.sym)
         );
 
+        // Generated code uses the span of `@FnApi` so source maps point to the
+        // decorator.
         {
             let var_decl = VarDeclarator {
-                span: DUMMY_SP,
+                span: api_ann_span,
                 name: config_object_name.clone().into(),
                 init: Some(box Expr::Call(CallExpr {
-                    span: DUMMY_SP,
+                    span: api_ann_span,
                     callee: self
                         .wrap_fnapi_config
                         .get_or_insert_with(|| private_ident!("wrapFnApiConfig"))
                         .clone()
                         .as_callee(),
-                    args: args_to_fn_api.unwrap_or_default(),
+                    args: decorators.args_to_fn_api.take().unwrap_or_default(),
                     type_args: Default::default(),
                 })),
                 definite: Default::default(),
             };
             self.stmts_to_prepend
                 .push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
-                    span: DUMMY_SP,
+                    span: api_ann_span,
                     kind: VarDeclKind::Const,
                     declare: Default::default(),
                     decls: vec![var_decl],
@@ -215,20 +364,71 @@ impl FileCompiler<'_> {
         let req_var = private_ident!(api_ann_span, "_req");
         let reply_var = private_ident!(api_ann_span, "_reply");
 
-        let body = match &mut method.function.body {
+        let body = match &mut function.body {
             Some(body) => body,
             None => {
                 HANDLER.with(|handler| {
                     handler
-                        .struct_span_err(method.span, "API method must have a body")
-                        .span_note(api_ann_span, "This method has the @FnApi decorator")
+                        .struct_span_err(function.span, "API functions must have a body")
+                        .span_note(api_ann_span, "This is marked as an API here")
                         .emit();
                 });
                 return None;
             }
         };
 
-        let stmts_for_param_init = if method.function.params.is_empty() {
+        let shapes = function
+            .params
+            .iter()
+            .map(param_shape)
+            .collect::<Option<Vec<_>>>()?;
+        let spans = function
+            .params
+            .iter()
+            .map(|param| param.span)
+            .collect::<Vec<_>>();
+        let names = function
+            .params
+            .iter()
+            .map(|param| param_name(param).map(|i| i.sym.clone()))
+            .collect::<Vec<_>>();
+        let optionals = function
+            .params
+            .iter()
+            .map(is_optional_param)
+            .collect::<Vec<_>>();
+
+        let mut locations = function
+            .params
+            .iter_mut()
+            .map(|param| self.take_param_location(param))
+            .collect::<Vec<_>>();
+
+        let (route, selected_by, decorator) = match &decorators.http {
+            Some((span, args)) => (
+                parse_http_route(*span, args, &function.params, &mut locations)?,
+                *span,
+                "@Http",
+            ),
+            None => (
+                HttpRoute {
+                    method: default_method,
+                    path: None,
+                },
+                api_ann_span,
+                "FnApi",
+            ),
+        };
+        check_body_of_get(
+            route.method,
+            selected_by,
+            decorator,
+            &function.params,
+            &locations,
+        )?;
+        let validations = parse_validations(&self.imports, &decorators.validate, &function.params)?;
+
+        let stmts_for_param_init = if function.params.is_empty() {
             vec![]
         } else {
             let mut stmts = vec![];
@@ -236,51 +436,36 @@ impl FileCompiler<'_> {
             let params_var = private_ident!("params");
 
             stmts.push(Stmt::Decl(Decl::Var(VarDecl {
-                span: DUMMY_SP,
+                span: api_ann_span,
                 kind: VarDeclKind::Const,
                 declare: Default::default(),
                 decls: vec![VarDeclarator {
-                    span: DUMMY_SP,
+                    span: api_ann_span,
                     name: params_var.clone().into(),
                     init: Some(box req_var.clone().make_member(quote_ident!("params"))),
                     definite: Default::default(),
                 }],
             })));
 
-            for (idx, param) in method.function.params.take().into_iter().enumerate() {
-                let init = box Expr::Member(MemberExpr {
-                    span: DUMMY_SP,
-                    obj: box params_var.clone().into(),
-                    prop: MemberProp::Computed(ComputedPropName {
-                        span: DUMMY_SP,
-                        expr: idx.into(),
-                    }),
-                });
-                stmts.push(Stmt::Decl(Decl::Var(VarDecl {
-                    span: DUMMY_SP,
-                    kind: VarDeclKind::Let,
-                    decls: vec![VarDeclarator {
-                        span: DUMMY_SP,
-                        name: param.pat,
-                        init: Some(init),
-                        definite: false,
-                    }],
-                    declare: false,
-                })));
+            // Properties of destructured parameters are sent as parameters.
+            let mut offset = 0;
+            for (param, shape) in function.params.take().into_iter().zip(&shapes) {
+                stmts.push(param_init(&params_var, param, shape, offset));
+                offset += shape.len();
             }
             stmts
         };
 
         // req
-        method.function.params.push(Param {
-            span: DUMMY_SP,
+        function.params.push(Param {
+            span: api_ann_span,
             decorators: Default::default(),
             pat: Pat::Ident(req_var.clone().into()),
         });
 
         // reply
-        method.function.params.push(Param {
-            span: DUMMY_SP,
+        function.params.push(Param {
+            span: api_ann_span,
             decorators: Default::default(),
             pat: Pat::Ident(reply_var.clone().into()),
         });
@@ -293,13 +478,13 @@ impl FileCompiler<'_> {
             self.imports.clone(),
         ));
 
-        let ret_ty =
-            self.extract_return_type(method.function.span, &method.function.return_type)?;
+        let ret_ty = self.extract_return_type(function.span, &function.return_type)?;
 
-        let method_types = self
-            .project
-            .type_server
-            .query_return_type_of_method_sync(self.filename, &name.sym);
+        let method_types = self.project.type_server.query_return_type_of_method_sync(
+            self.filename,
+            class_name.map(|v| &**v),
+            &name.sym,
+        );
 
         let method_types = match method_types {
             Ok(v) => v,
@@ -316,23 +501,148 @@ impl FileCompiler<'_> {
             }
         };
 
+        // Parameters are matched by index, so a parameter would be dropped
+        // from the request if the signature of the type server differs, like
+        // for overloads.
+        if method_types.params.len() != shapes.len()
+            || (!method_types.param_names.is_empty()
+                && method_types.param_names.len() != shapes.len())
+        {
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_err(
+                        function.span,
+                        &format!(
+                            "The type checker found {} parameter(s), but the function has {}",
+                            method_types.params.len(),
+                            shapes.len()
+                        ),
+                    )
+                    .span_note(api_ann_span, "This is marked as an API here")
+                    .help("API functions can't be overloaded or have a `this` parameter")
+                    .emit();
+            });
+            return None;
+        }
+
+        let jsdoc = self.find_jsdoc(doc_pos);
+
+        let mut params = vec![];
+        let mut has_error = false;
+        for (idx, ((shape, ty), (location, validations))) in shapes
+            .iter()
+            .zip(&method_types.params)
+            .zip(locations.into_iter().zip(validations))
+            .enumerate()
+        {
+            match shape {
+                ParamShape::Value | ParamShape::Rest => params.push(ApiParam {
+                    name: names[idx].clone().or_else(|| {
+                        // The type server names destructured parameters like `__0`.
+                        method_types
+                            .param_names
+                            .get(idx)
+                            .filter(|name| !name.starts_with("__"))
+                            .map(|name| JsWord::from(&**name))
+                    }),
+                    location,
+                    ty: Arc::new(ty.clone()),
+                    optional: optionals[idx],
+                    rest: matches!(shape, ParamShape::Rest),
+                    properties: vec![],
+                    validations,
+                    description: names[idx]
+                        .as_ref()
+                        .and_then(|name| jsdoc.param(name, false)),
+                }),
+                ParamShape::Destructured(props) => {
+                    let prop_types = match destructured_prop_types(spans[idx], ty, props) {
+                        Some(v) => v,
+                        None => {
+                            has_error = true;
+                            continue;
+                        }
+                    };
+
+                    params.push(ApiParam {
+                        name: None,
+                        location,
+                        ty: Arc::new(ty.clone()),
+                        optional: optionals[idx],
+                        rest: false,
+                        properties: props
+                            .iter()
+                            .zip(prop_types)
+                            .map(|(prop, (ty, optional))| ApiParam {
+                                name: Some(prop.key.sym.clone()),
+                                location,
+                                ty: Arc::new(ty),
+                                optional: optional || prop.has_default || optionals[idx],
+                                rest: false,
+                                properties: vec![],
+                                validations: vec![],
+                                description: jsdoc.param(&prop.key.sym, true),
+                            })
+                            .collect(),
+                        validations,
+                        description: None,
+                    });
+                }
+            }
+        }
+        if has_error {
+            return None;
+        }
+
+        // Keys are the same as `ApiFn::request_params`, and a parameter would
+        // overwrite another one with the same key, like `p1` of `[a, b]`.
+        let mut keys: Vec<(String, Span)> = vec![];
+        for (idx, (param, shape)) in params.iter().zip(&shapes).enumerate() {
+            let param_keys = match shape {
+                ParamShape::Destructured(props) => param
+                    .properties
+                    .iter()
+                    .zip(props)
+                    .enumerate()
+                    .map(|(prop_idx, (prop, p))| (prop.key(prop_idx), p.key.span))
+                    .collect(),
+                _ => vec![(param.key(idx), spans[idx])],
+            };
+
+            for (key, span) in param_keys {
+                if let Some((_, prev)) = keys.iter().find(|(k, _)| *k == key) {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_err(
+                                span,
+                                &format!("The parameter `{}` is sent more than once", key),
+                            )
+                            .span_note(*prev, "The key is also used here")
+                            .help("Rename one of the parameters")
+                            .emit();
+                    });
+                    has_error = true;
+                }
+                keys.push((key, span));
+            }
+        }
+        if has_error {
+            return None;
+        }
+
         {
             // Create a record
             self.compiled_method_records.push(MethodRecord {
                 name: name.sym.clone(),
                 config_object_var_name: config_object_name,
+                has_http: decorators.http.is_some(),
                 api_def: Arc::new(ApiFn {
-                    name: name.sym,
-                    params: method_types
-                        .params
-                        .iter()
-                        .map(|ty| ApiParam {
-                            // TODO
-                            name: None,
-                            ty: Arc::new(ty.clone()),
-                        })
-                        .collect(),
-                    return_type: Arc::new(method_types.return_type.clone()),
+                    name: name.sym.clone(),
+                    method: route.method,
+                    path: route.path,
+                    params,
+                    return_type: Arc::new(method_types.return_type),
+                    docs: jsdoc.docs,
                 }),
             });
         }
@@ -340,6 +650,164 @@ impl FileCompiler<'_> {
         None
     }
 
+    /// Removes `@Query()`, `@Path()`, `@Header()` or `@Body()` from `param`,
+    /// and returns the location selected by it.
+    fn take_param_location(&self, param: &mut Param) -> ParamLocation {
+        let mut location = None;
+
+        param.decorators.retain(|dec| {
+            let callee = match &*dec.expr {
+                Expr::Call(CallExpr {
+                    callee: Callee::Expr(callee),
+                    ..
+                }) => callee,
+                _ => return true,
+            };
+
+            let found = PARAM_DECORATORS
+                .iter()
+                .find(|(name, _)| self.imports.is_import(callee, FNAPI_API_MODULE, name));
+
+            match found {
+                Some((_, found)) => {
+                    if location.replace(*found).is_some() {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(
+                                    dec.span,
+                                    "A parameter can have only one of @Query, @Path, @Header and \
+                                     @Body",
+                                )
+                                .emit();
+                        });
+                    }
+                    false
+                }
+                None => true,
+            }
+        });
+
+        location.unwrap_or_default()
+    }
+
+    /// Compiles an exported function, which is served with the routes of the
+    /// file.
+    fn compile_exported_fn(
+        &mut self,
+        name: &Ident,
+        api_ann_span: Span,
+        doc_pos: BytePos,
+        args_to_fn_api: Option<Vec<ExprOrSpread>>,
+        function: &mut Function,
+    ) {
+        let len = self.compiled_method_records.len();
+
+        self.compile_api_fn(
+            None,
+            name,
+            api_ann_span,
+            doc_pos,
+            ApiDecorators {
+                args_to_fn_api,
+                ..Default::default()
+            },
+            function,
+        );
+
+        if self.compiled_method_records.len() > len {
+            self.api_fns.push(name.clone());
+        }
+    }
+
+    /// Compiles `export const login = FnApi(async () => {})`.
+    ///
+    /// `doc_pos` is the start of the export declaration.
+    fn compile_wrapped_fn(&mut self, decl: &mut VarDeclarator, doc_pos: BytePos) -> Option<!> {
+        let name = match &decl.name {
+            Pat::Ident(i) => i.id.clone(),
+            _ => return None,
+        };
+
+        let (api_ann_span, args) = match decl.init.as_deref_mut() {
+            Some(Expr::Call(CallExpr {
+                callee: Callee::Expr(callee),
+                args,
+                ..
+            })) if self.imports.is_import(callee, FNAPI_API_MODULE, "FnApi") => {
+                (callee.span(), args)
+            }
+            _ => return None,
+        };
+
+        let mut function = match args.pop() {
+            Some(ExprOrSpread {
+                spread: None,
+                expr: box Expr::Fn(f),
+            }) => f.function,
+            Some(ExprOrSpread {
+                spread: None,
+                expr: box Expr::Arrow(f),
+            }) => {
+                // The arrow function is compiled into a function, which has
+                // its own `this` and `arguments`.
+                if let Some((span, name)) = find_this_or_arguments(&f) {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_err(
+                                span,
+                                &format!("Arrow functions wrapped by FnApi can't use `{}`", name),
+                            )
+                            .help(
+                                "Use a function expression like 'FnApi(async function () {})' \
+                                 instead",
+                            )
+                            .emit();
+                    });
+                    return None;
+                }
+
+                arrow_to_function(f)
+            }
+            _ => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(api_ann_span, "FnApi() should wrap a function")
+                        .help("Use it like 'export const login = FnApi(async () => {})'")
+                        .emit();
+                });
+                return None;
+            }
+        };
+        let args = args.take();
+
+        self.compile_exported_fn(&name, api_ann_span, doc_pos, Some(args), &mut function);
+
+        decl.init = Some(box Expr::Fn(FnExpr {
+            ident: None,
+            function,
+        }));
+
+        None
+    }
+
+    /// Returns the span of the `@fnapi` JSDoc comment of the node at `pos`.
+    fn find_fnapi_jsdoc(&self, pos: BytePos) -> Option<Span> {
+        self.comments
+            .get(&pos)?
+            .iter()
+            .find(|c| is_fnapi_jsdoc(c))
+            .map(|c| c.span)
+    }
+
+    /// Parses the last JSDoc comment of the node at `pos`.
+    fn find_jsdoc(&self, pos: BytePos) -> JsDoc {
+        self.comments
+            .get(&pos)
+            .and_then(|comments| comments.iter().rev().find(|c| is_jsdoc(c)))
+            .map(|c| parse_jsdoc(&c.text))
+            .unwrap_or_default()
+    }
+
     fn extract_return_type(&self, method_span: Span, ty: &Option<TsTypeAnn>) -> Option<TsType> {
         let ty = match ty {
             Some(ret_ty) => ret_ty,
@@ -403,33 +871,344 @@ impl FileCompiler<'_> {
     }
 }
 
+impl FileCompiler<'_> {
+    /// Compiles api methods of `class`, and returns the arguments to
+    /// `wrapApiClass` following the class.
+    ///
+    /// Returns [None] if `class` does not have any api method.
+    fn compile_api_class(
+        &mut self,
+        class_name: JsWord,
+        is_default_export: bool,
+        class: &mut Class,
+    ) -> Option<Vec<ExprOrSpread>> {
+        let old_class_name = self.class_name.replace(class_name.clone());
+        let old_records = take(&mut self.compiled_method_records);
+
+        class.visit_mut_with(self);
+
+        self.class_name = old_class_name;
+        let records = replace(&mut self.compiled_method_records, old_records);
+
+        if records.is_empty() {
+            return None;
+        }
+
+        self.classes.push(Arc::new(ApiClass {
+            class_name,
+            is_default_export,
+            functions: records.iter().map(|v| v.api_def.clone()).collect(),
+        }));
+
+        Some(self.wrap_api_class_args(&records, None))
+    }
+
+    /// Creates arguments to `wrapApiClass` following the class.
+    ///
+    /// `name` overrides the class name used for routes.
+    fn wrap_api_class_args(
+        &self,
+        records: &[MethodRecord],
+        name: Option<&str>,
+    ) -> Vec<ExprOrSpread> {
+        let mut args = vec![ArrayLit {
+            span: DUMMY_SP,
+            elems: records
+                .iter()
+                .map(|method_record| ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![
+                        PropOrSpread::Spread(SpreadElement {
+                            dot3_token: DUMMY_SP,
+                            expr: box method_record.config_object_var_name.clone().into(),
+                        }),
+                        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                            key: quote_ident!("name").into(),
+                            value: box method_record.name.clone().into(),
+                        })),
+                        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                            key: quote_ident!("parameterNames").into(),
+                            value: box ArrayLit {
+                                span: DUMMY_SP,
+                                elems: method_record
+                                    .api_def
+                                    .request_params()
+                                    .into_iter()
+                                    .map(|(key, _)| Expr::from(&*key).as_arg())
+                                    .map(Some)
+                                    .collect(),
+                            }
+                            .into(),
+                        })),
+                        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                            key: quote_ident!("parameterTypes").into(),
+                            value: box ArrayLit {
+                                span: DUMMY_SP,
+                                elems: method_record
+                                    .api_def
+                                    .request_params()
+                                    .into_iter()
+                                    .map(|(_, param)| param.to_js_expr().as_arg())
+                                    .map(Some)
+                                    .collect(),
+                            }
+                            .into(),
+                        })),
+                        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                            key: quote_ident!("returnType").into(),
+                            value: method_record.api_def.return_type.to_js_expr(),
+                        })),
+                    ]
+                    .into_iter()
+                    .chain(
+                        method_record
+                            .has_http
+                            .then(|| self.http_route_props(&method_record.api_def))
+                            .into_iter()
+                            .flatten(),
+                    )
+                    .chain(response_projector(&method_record.api_def.return_type).map(
+                        |projector| {
+                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                key: quote_ident!("projectResponse").into(),
+                                value: projector,
+                            }))
+                        },
+                    ))
+                    .chain(self.optional_param_names_prop(&method_record.api_def))
+                    .chain(LOCATION_SCHEMAS.iter().filter_map(|(key, location)| {
+                        let schema = method_record.api_def.params_schema(*location)?;
+
+                        Some(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                            key: quote_ident!(*key).into(),
+                            value: schema.to_js_expr(),
+                        })))
+                    }))
+                    .collect(),
+                })
+                .map(|v| v.as_arg())
+                .map(Some)
+                .collect(),
+        }
+        .as_arg()];
+
+        let mut options = vec![];
+        if let Some(prefix) = &self.project.route_prefix {
+            options.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: quote_ident!("prefix").into(),
+                value: box Expr::from(&**prefix),
+            })));
+        }
+        if let Some(name) = name {
+            options.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: quote_ident!("name").into(),
+                value: box Expr::from(name),
+            })));
+        }
+        if !options.is_empty() {
+            args.push(
+                ObjectLit {
+                    span: DUMMY_SP,
+                    props: options,
+                }
+                .as_arg(),
+            );
+        }
+
+        args
+    }
+
+    /// Creates `optionalParameterNames`, which is omitted if every parameter
+    /// is required.
+    fn optional_param_names_prop(&self, f: &ApiFn) -> Option<PropOrSpread> {
+        let names = f
+            .request_params()
+            .into_iter()
+            .filter(|(_, param)| !param.is_required())
+            .map(|(key, _)| Some(Expr::from(&*key).as_arg()))
+            .collect::<Vec<_>>();
+
+        if names.is_empty() {
+            return None;
+        }
+
+        Some(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+            key: quote_ident!("optionalParameterNames").into(),
+            value: box ArrayLit {
+                span: DUMMY_SP,
+                elems: names,
+            }
+            .into(),
+        })))
+    }
+
+    /// Creates properties of a method descriptor which override the route
+    /// selected by `@FnApi`.
+    fn http_route_props(&self, api: &ApiFn) -> Vec<PropOrSpread> {
+        let mut props = vec![PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+            key: quote_ident!("httpMethod").into(),
+            value: box Expr::from(api.method.as_str()),
+        }))];
+        if let Some(path) = &api.path {
+            props.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: quote_ident!("path").into(),
+                value: box Expr::from(&**path),
+            })));
+        }
+        props
+    }
+
+    /// Creates `export const auth = wrapApiClass({ login: login }, ...)` for
+    /// exported api functions, which are routed by the name of the file.
+    fn wrap_api_fns(&mut self) -> Option<ModuleItem> {
+        let records = take(&mut self.compiled_method_records);
+        let api_fns = take(&mut self.api_fns);
+
+        if records.is_empty() {
+            return None;
+        }
+
+        let name = ServerApiFile::function_group_name(Path::new(self.filename));
+
+        // `export const auth` would conflict with the function.
+        if let Some(f) = api_fns.iter().find(|f| *f.sym == *name) {
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_err(
+                        f.span,
+                        &format!(
+                            "API functions should not be named after the file ('{}')",
+                            name
+                        ),
+                    )
+                    .help("Exported api functions are served using the name of the file")
+                    .emit();
+            });
+            return None;
+        }
+
+        self.classes.push(Arc::new(ApiClass {
+            class_name: name.clone().into(),
+            is_default_export: false,
+            functions: records.iter().map(|v| v.api_def.clone()).collect(),
+        }));
+
+        let obj = ObjectLit {
+            span: DUMMY_SP,
+            props: records
+                .iter()
+                .zip(api_fns)
+                .map(|(record, local)| {
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: quote_ident!(record.name.clone()).into(),
+                        value: box local.into(),
+                    }))
+                })
+                .collect(),
+        };
+
+        let args = self.wrap_api_class_args(&records, Some(&name));
+        let expr = self.wrap_api_class(DUMMY_SP, obj.into(), args);
+
+        Some(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            span: DUMMY_SP,
+            decl: Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Const,
+                declare: Default::default(),
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: quote_ident!(&*name).into(),
+                    init: Some(box expr),
+                    definite: Default::default(),
+                }],
+            }),
+        })))
+    }
+
+    /// Creates `wrapApiClass(class, ...args)`.
+    fn wrap_api_class(&mut self, span: Span, class: Expr, args: Vec<ExprOrSpread>) -> Expr {
+        let wrapper = self
+            .wrap_api_class
+            .get_or_insert_with(|| private_ident!("wrapApiClass"))
+            .clone();
+
+        Expr::Call(CallExpr {
+            span,
+            callee: wrapper.as_callee(),
+            args: once(class.as_arg()).chain(args).collect(),
+            type_args: Default::default(),
+        })
+    }
+}
+
 impl VisitMut for FileCompiler<'_> {
     fn visit_mut_class_method(&mut self, n: &mut ClassMethod) {
         n.visit_mut_children_with(self);
 
-        if !self.in_export_default_decl {
-            return;
+        if let Some(class_name) = self.class_name.clone() {
+            self.compile_api_method(&class_name, n);
+        }
+    }
+
+    fn visit_mut_module(&mut self, n: &mut Module) {
+        n.visit_mut_children_with(self);
+
+        if let Some(item) = self.wrap_api_fns() {
+            self.stmts_to_append.push(item);
+        }
+
+        // Imports are shared by all api classes in the file.
+        let mut imports = vec![];
+        if let Some(local) = &self.wrap_api_class {
+            imports.push(default_import(
+                local.clone(),
+                self.target.wrap_api_class_import_path(),
+            ));
+        }
+        if let Some(local) = &self.wrap_fnapi_config {
+            imports.push(default_import(
+                local.clone(),
+                "@fnapi/api/rt/wrapFnApiConfig.js".into(),
+            ));
         }
 
-        self.compile_api_method(n);
+        prepend_stmts(self.stmts_to_prepend, imports.into_iter());
     }
 
-    fn visit_mut_export_default_decl(&mut self, n: &mut ExportDefaultDecl) {
-        match &n.decl {
-            DefaultDecl::Class(cls) => {
-                if cls.ident.is_none() {
-                    HANDLER.with(|handler| {
-                        handler
-                            .struct_span_err(cls.class.span, "All API classes should have a name")
-                            .emit();
+    fn visit_mut_module_decl(&mut self, n: &mut ModuleDecl) {
+        match n {
+            ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                span,
+                decl: DefaultDecl::Class(cls),
+            }) => {
+                let class_name = match &cls.ident {
+                    Some(ident) => ident.sym.clone(),
+                    None => {
+                        HANDLER.with(|handler| {
+                            handler
+                                .struct_span_err(
+                                    cls.class.span,
+                                    "All API classes should have a name",
+                                )
+                                .emit();
+                        });
+                        return;
+                    }
+                };
+
+                if let Some(args) = self.compile_api_class(class_name, true, &mut cls.class) {
+                    let span = *span;
+                    let expr = self.wrap_api_class(span, Expr::Class(cls.take()), args);
+
+                    *n = ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                        span,
+                        expr: box expr,
                     });
-                } else {
-                    self.class_name = cls.ident.clone().unwrap().sym;
                 }
-
-                // TODO: Ends with Api?
             }
-            _ => {
+
+            ModuleDecl::ExportDefaultDecl(n) => {
                 HANDLER.with(|handler| {
                     handler
                         .struct_span_err(
@@ -439,117 +1218,167 @@ impl VisitMut for FileCompiler<'_> {
                         )
                         .emit();
                 });
-                return;
             }
+
+            // `export class UserApi {}` becomes
+            // `export const UserApi = wrapApiClass(class UserApi {}, ...)`
+            ModuleDecl::ExportDecl(ExportDecl {
+                span,
+                decl: Decl::Class(cls),
+            }) => {
+                if let Some(args) =
+                    self.compile_api_class(cls.ident.sym.clone(), false, &mut cls.class)
+                {
+                    let span = *span;
+                    let ident = cls.ident.clone();
+                    let expr = self.wrap_api_class(
+                        span,
+                        Expr::Class(ClassExpr {
+                            ident: Some(ident.clone()),
+                            class: cls.class.take(),
+                        }),
+                        args,
+                    );
+
+                    *n = ModuleDecl::ExportDecl(ExportDecl {
+                        span,
+                        decl: Decl::Var(VarDecl {
+                            span,
+                            kind: VarDeclKind::Const,
+                            declare: Default::default(),
+                            decls: vec![VarDeclarator {
+                                span,
+                                name: ident.into(),
+                                init: Some(box expr),
+                                definite: Default::default(),
+                            }],
+                        }),
+                    });
+                }
+            }
+
+            // `/** @fnapi */ export async function login() {}`
+            ModuleDecl::ExportDecl(ExportDecl {
+                span,
+                decl: Decl::Fn(f),
+            }) => {
+                f.visit_mut_children_with(self);
+
+                if let Some(api_ann_span) = self.find_fnapi_jsdoc(span.lo) {
+                    self.compile_exported_fn(
+                        &f.ident,
+                        api_ann_span,
+                        span.lo,
+                        None,
+                        &mut f.function,
+                    );
+                }
+            }
+
+            ModuleDecl::ExportDecl(ExportDecl {
+                span,
+                decl: Decl::Var(var),
+            }) => {
+                var.visit_mut_children_with(self);
+
+                for decl in &mut var.decls {
+                    self.compile_wrapped_fn(decl, span.lo);
+                }
+            }
+
+            _ => n.visit_mut_children_with(self),
         }
+    }
+}
 
-        let old = self.in_export_default_decl;
-        self.in_export_default_decl = true;
-        n.visit_mut_children_with(self);
-        self.in_export_default_decl = old;
+/// Returns true if `c` is a JSDoc comment with an `@fnapi` tag.
+fn is_fnapi_jsdoc(c: &Comment) -> bool {
+    is_jsdoc(c)
+        && c.text.match_indices("@fnapi").any(|(idx, tag)| {
+            !c.text[idx + tag.len()..]
+                .starts_with(|c: char| c.is_alphanumeric() || c == '_' || c == '-' || c == '/')
+        })
+}
+
+/// Converts `(a) => a` to `function (a) { return a }`.
+fn arrow_to_function(f: ArrowExpr) -> Function {
+    let body = match f.body {
+        BlockStmtOrExpr::BlockStmt(body) => body,
+        BlockStmtOrExpr::Expr(expr) => BlockStmt {
+            span: expr.span(),
+            stmts: vec![Stmt::Return(ReturnStmt {
+                span: expr.span(),
+                arg: Some(expr),
+            })],
+        },
+    };
+
+    Function {
+        params: f
+            .params
+            .into_iter()
+            .map(|pat| Param {
+                span: pat.span(),
+                decorators: Default::default(),
+                pat,
+            })
+            .collect(),
+        decorators: Default::default(),
+        span: f.span,
+        body: Some(body),
+        is_generator: f.is_generator,
+        is_async: f.is_async,
+        type_params: f.type_params,
+        return_type: f.return_type,
     }
+}
 
-    fn visit_mut_module_decl(&mut self, n: &mut ModuleDecl) {
-        n.visit_mut_children_with(self);
+/// Returns the first `this` or `arguments` of the arrow function `f`, with
+/// the name.
+fn find_this_or_arguments(f: &ArrowExpr) -> Option<(Span, &'static str)> {
+    let mut finder = ThisFinder::default();
+    f.visit_with(&mut finder);
+    finder.found
+}
 
-        if !self.compiled_method_records.is_empty() {
-            if let ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
-                span,
-                decl: DefaultDecl::Class(cls),
-            }) = n
-            {
-                let wrapper = private_ident!("wrapApiClass");
-
-                let wrap_api_class_import =
-                    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                        span: *span,
-                        specifiers: vec![ImportSpecifier::Default(ImportDefaultSpecifier {
-                            span: DUMMY_SP,
-                            local: wrapper.clone(),
-                        })],
-                        src: self.target.wrap_api_class_import_path().into(),
-                        type_only: false,
-                        asserts: Default::default(),
-                    }));
+#[derive(Default)]
+struct ThisFinder {
+    found: Option<(Span, &'static str)>,
+}
 
-                let wrap_api_config_import =
-                    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                        span: *span,
-                        specifiers: vec![ImportSpecifier::Default(ImportDefaultSpecifier {
-                            span: DUMMY_SP,
-                            local: self.wrap_fnapi_config.clone().unwrap(),
-                        })],
-                        src: "@fnapi/api/rt/wrapFnApiConfig.js".into(),
-                        type_only: false,
-                        asserts: Default::default(),
-                    }));
+impl Visit for ThisFinder {
+    fn visit_expr(&mut self, e: &Expr) {
+        if self.found.is_some() {
+            return;
+        }
 
-                prepend_stmts(
-                    self.stmts_to_prepend,
-                    vec![wrap_api_class_import, wrap_api_config_import].into_iter(),
-                );
-
-                *n = ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
-                    span: *span,
-                    expr: box Expr::Call(CallExpr {
-                        span: DUMMY_SP,
-                        callee: wrapper.as_callee(),
-                        args: vec![
-                            cls.take().as_arg(),
-                            ArrayLit {
-                                span: DUMMY_SP,
-                                elems: self
-                                    .compiled_method_records
-                                    .iter()
-                                    .map(|method_record| ObjectLit {
-                                        span: DUMMY_SP,
-                                        props: vec![
-                                            PropOrSpread::Spread(SpreadElement {
-                                                dot3_token: DUMMY_SP,
-                                                expr: box method_record
-                                                    .config_object_var_name
-                                                    .clone()
-                                                    .into(),
-                                            }),
-                                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
-                                                key: quote_ident!("name").into(),
-                                                value: box method_record.name.clone().into(),
-                                            })),
-                                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
-                                                key: quote_ident!("parameterTypes").into(),
-                                                value: box ArrayLit {
-                                                    span: DUMMY_SP,
-                                                    elems: method_record
-                                                        .api_def
-                                                        .params
-                                                        .iter()
-                                                        .map(|param| param.ty.to_js_expr().as_arg())
-                                                        .map(Some)
-                                                        .collect(),
-                                                }
-                                                .into(),
-                                            })),
-                                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
-                                                key: quote_ident!("returnType").into(),
-                                                value: method_record
-                                                    .api_def
-                                                    .return_type
-                                                    .to_js_expr(),
-                                            })),
-                                        ],
-                                    })
-                                    .map(|v| v.as_arg())
-                                    .map(Some)
-                                    .collect(),
-                            }
-                            .as_arg(),
-                        ],
-                        type_args: Default::default(),
-                    }),
-                });
+        match e {
+            Expr::This(ThisExpr { span }) => self.found = Some((*span, "this")),
+            Expr::Ident(Ident { span, sym, .. }) if &**sym == "arguments" => {
+                self.found = Some((*span, "arguments"))
             }
+            _ => e.visit_children_with(self),
         }
     }
+
+    // Functions and classes have their own `this` and `arguments`.
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_class(&mut self, _: &Class) {}
+}
+
+/// Creates `import local from "src"`.
+fn default_import(local: Ident, src: JsWord) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+        span: DUMMY_SP,
+        specifiers: vec![ImportSpecifier::Default(ImportDefaultSpecifier {
+            span: DUMMY_SP,
+            local,
+        })],
+        src: src.into(),
+        type_only: false,
+        asserts: Default::default(),
+    }))
 }
 
 fn get_span_of_api_decorator(imports: &ImportMap, decorators: &[Decorator]) -> Option<Span> {
@@ -0,0 +1,291 @@
+//! Shapes of parameters of api functions.
+
+use fnapi_api_def::types::{Type, TypeElement};
+use swc_common::{errors::HANDLER, Span, Spanned, DUMMY_SP};
+use swc_ecmascript::{ast::*, utils::quote_ident};
+
+/// How a parameter of the signature is sent in requests.
+#[derive(Debug)]
+pub(super) enum ParamShape {
+    /// Sent as a value, like `id: string` or `[a, b]: Pair`.
+    Value,
+    /// `...tags: string[]`, which is sent as an array.
+    Rest,
+    /// `{ title, done = false }: CreateTodo`, whose properties are sent as
+    /// parameters.
+    Destructured(Vec<DestructuredProp>),
+}
+
+/// A property of a destructured parameter.
+#[derive(Debug)]
+pub(super) struct DestructuredProp {
+    pub key: Ident,
+    pub has_default: bool,
+}
+
+impl ParamShape {
+    /// Returns the number of parameters sent for the parameter.
+    pub fn len(&self) -> usize {
+        match self {
+            ParamShape::Value | ParamShape::Rest => 1,
+            ParamShape::Destructured(props) => props.len(),
+        }
+    }
+}
+
+/// Returns the name of `param`, like `limit` of `limit = 10` or `tags` of
+/// `...tags`.
+///
+/// Returns [None] for destructured parameters.
+pub(super) fn param_name(param: &Param) -> Option<&Ident> {
+    match &param.pat {
+        Pat::Ident(i)
+        | Pat::Assign(AssignPat {
+            left: box Pat::Ident(i),
+            ..
+        })
+        | Pat::Rest(RestPat {
+            arg: box Pat::Ident(i),
+            ..
+        }) => Some(&i.id),
+        _ => None,
+    }
+}
+
+/// Returns true if `param` may be omitted, like `limit?: number`,
+/// `limit = 10` or `...tags`.
+pub(super) fn is_optional_param(param: &Param) -> bool {
+    match &param.pat {
+        Pat::Ident(i) => i.id.optional,
+        Pat::Array(a) => a.optional,
+        Pat::Object(o) => o.optional,
+        Pat::Assign(..) | Pat::Rest(..) => true,
+        _ => false,
+    }
+}
+
+/// Returns the shape of `param`, or [None] if the pattern is not supported.
+pub(super) fn param_shape(param: &Param) -> Option<ParamShape> {
+    match &param.pat {
+        Pat::Ident(..) | Pat::Array(..) => Some(ParamShape::Value),
+        Pat::Object(obj) if obj.props.is_empty() => Some(ParamShape::Value),
+        Pat::Assign(AssignPat { left, .. }) => match &**left {
+            Pat::Ident(..) | Pat::Array(..) => Some(ParamShape::Value),
+            _ => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(
+                            param.span,
+                            "Destructured parameters of api functions can't have a default value",
+                        )
+                        .help("Use default values of properties, like '{ limit = 10 }'")
+                        .emit();
+                });
+                None
+            }
+        },
+        Pat::Rest(RestPat {
+            arg: box Pat::Ident(..),
+            ..
+        }) => Some(ParamShape::Rest),
+        Pat::Rest(..) => {
+            unsupported(
+                param.span,
+                "Rest parameters of api functions should be identifiers",
+            );
+            None
+        }
+        Pat::Object(obj) => {
+            let mut props = vec![];
+            for prop in &obj.props {
+                props.push(match prop {
+                    ObjectPatProp::Assign(AssignPatProp { key, value, .. }) => DestructuredProp {
+                        key: key.clone(),
+                        has_default: value.is_some(),
+                    },
+                    ObjectPatProp::KeyValue(KeyValuePatProp {
+                        key: PropName::Ident(key),
+                        value,
+                    }) => DestructuredProp {
+                        key: key.clone(),
+                        has_default: matches!(&**value, Pat::Assign(..)),
+                    },
+                    ObjectPatProp::KeyValue(KeyValuePatProp { key, .. }) => {
+                        unsupported_prop(
+                            key.span(),
+                            "Keys of destructured parameters should be identifiers",
+                        );
+                        return None;
+                    }
+                    ObjectPatProp::Rest(rest) => {
+                        unsupported_prop(
+                            rest.span,
+                            "Rest properties of destructured parameters are not supported",
+                        );
+                        return None;
+                    }
+                });
+            }
+            Some(ParamShape::Destructured(props))
+        }
+        _ => {
+            unsupported(param.span, "This parameter pattern is not supported");
+            None
+        }
+    }
+}
+
+/// Returns the properties of `ty` for `props`, as `(type, optional)`.
+///
+/// Returns [None] if `ty` is not an object type with the properties.
+pub(super) fn destructured_prop_types(
+    span: Span,
+    ty: &Type,
+    props: &[DestructuredProp],
+) -> Option<Vec<(Type, bool)>> {
+    let members = match ty {
+        Type::Object(obj) => &obj.members,
+        _ => {
+            HANDLER.with(|handler| {
+                handler
+                    .struct_span_err(span, "Destructured parameters should have an object type")
+                    .emit();
+            });
+            return None;
+        }
+    };
+
+    props
+        .iter()
+        .map(|prop| {
+            let found = members.iter().find_map(|member| match member {
+                TypeElement::Property(p) if *p.name == *prop.key.sym => {
+                    Some(((*p.ty).clone(), p.optional))
+                }
+                _ => None,
+            });
+
+            if found.is_none() {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(
+                            prop.key.span,
+                            &format!("'{}' is not a property of the parameter type", prop.key.sym),
+                        )
+                        .emit();
+                });
+            }
+            found
+        })
+        .collect()
+}
+
+/// Creates the declaration of `param`, which reads parameters of the request
+/// from `params[offset]`.
+pub(super) fn param_init(
+    params_var: &Ident,
+    param: Param,
+    shape: &ParamShape,
+    offset: usize,
+) -> Stmt {
+    let value = |idx: usize| {
+        box Expr::Member(MemberExpr {
+            span: param.span,
+            obj: box params_var.clone().into(),
+            prop: MemberProp::Computed(ComputedPropName {
+                span: DUMMY_SP,
+                expr: idx.into(),
+            }),
+        })
+    };
+
+    let (pat, init) = match (param.pat, shape) {
+        // `let limit = params[1] === undefined ? 10 : params[1]`
+        (
+            Pat::Assign(AssignPat {
+                left,
+                right: default,
+                ..
+            }),
+            _,
+        ) => (*left, or_default(param.span, value(offset), default)),
+
+        // `let tags = params[1] === undefined ? [] : params[1]`
+        (Pat::Rest(RestPat { arg, .. }), _) => (
+            *arg,
+            or_default(
+                param.span,
+                value(offset),
+                box Expr::Array(ArrayLit {
+                    span: DUMMY_SP,
+                    elems: vec![],
+                }),
+            ),
+        ),
+
+        // `let { title, done = false } = { title: params[0], done: params[1] }`
+        (pat, ParamShape::Destructured(props)) => (
+            pat,
+            box Expr::Object(ObjectLit {
+                span: param.span,
+                props: props
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, prop)| {
+                        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                            key: PropName::Ident(quote_ident!(prop.key.sym.clone())),
+                            value: value(offset + idx),
+                        }))
+                    })
+                    .collect(),
+            }),
+        ),
+
+        (pat, _) => (pat, value(offset)),
+    };
+
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: param.span,
+        kind: VarDeclKind::Let,
+        decls: vec![VarDeclarator {
+            span: param.span,
+            name: pat,
+            init: Some(init),
+            definite: false,
+        }],
+        declare: false,
+    }))
+}
+
+/// Creates `value === undefined ? default : value`.
+fn or_default(span: Span, value: Box<Expr>, default: Box<Expr>) -> Box<Expr> {
+    box Expr::Cond(CondExpr {
+        span,
+        test: box Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            op: op!("==="),
+            left: value.clone(),
+            right: box quote_ident!("undefined").into(),
+        }),
+        cons: default,
+        alt: value,
+    })
+}
+
+fn unsupported(span: Span, msg: &str) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_err(span, msg)
+            .help("Use an identifier, a destructured object or a rest parameter")
+            .emit();
+    });
+}
+
+fn unsupported_prop(span: Span, msg: &str) {
+    HANDLER.with(|handler| {
+        handler
+            .struct_span_err(span, msg)
+            .help("Destructure properties by name, like '{ title, done }'")
+            .emit();
+    });
+}
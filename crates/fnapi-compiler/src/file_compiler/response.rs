@@ -0,0 +1,384 @@
+//! Projection of return values to the declared return type.
+
+use std::mem::take;
+
+use fnapi_api_def::types::{Type, TypeElement};
+use swc_common::DUMMY_SP;
+use swc_ecmascript::{
+    ast::*,
+    utils::{member_expr, private_ident, quote_ident, ExprFactory},
+};
+
+/// Creates `(v)=>...`, which deep-copies the declared properties of the
+/// return value, so fields not in `ty` are never sent.
+///
+/// Returns [None] if `ty` does not contain an object or a tuple, as the value
+/// is sent as-is. Instances of builtin classes like `Date` are also sent
+/// as-is.
+pub(super) fn response_projector(ty: &Type) -> Option<Box<Expr>> {
+    let shape = Shape::of(ty);
+    if !shape.needs_projection() {
+        return None;
+    }
+
+    let value = private_ident!("v");
+    Some(box Expr::Arrow(ArrowExpr {
+        span: DUMMY_SP,
+        params: vec![Pat::Ident(value.clone().into())],
+        body: BlockStmtOrExpr::Expr(shape.project(box value.into(), 0)),
+        is_async: false,
+        is_generator: false,
+        type_params: Default::default(),
+        return_type: Default::default(),
+    }))
+}
+
+/// Declared structure of a value. Members of unions and intersections are
+/// merged, as the member of a value is not known until runtime.
+#[derive(Debug, Default)]
+struct Shape {
+    /// Declared properties, if the value may be an object.
+    props: Option<Vec<(String, Shape)>>,
+    /// Values of an index signature, like `Todo` of `Record<string, Todo>`.
+    index: Option<Box<Shape>>,
+    /// Declared elements, if the value may be an array.
+    items: Option<Items>,
+}
+
+#[derive(Debug)]
+enum Items {
+    Array(Box<Shape>),
+    Tuple(Vec<Shape>),
+}
+
+impl Shape {
+    fn of(ty: &Type) -> Self {
+        match ty {
+            Type::Keyword(..) => Shape::default(),
+            Type::Array(ty) => Shape {
+                items: Some(Items::Array(box Shape::of(&ty.elem))),
+                ..Default::default()
+            },
+            Type::Tuple(ty) => Shape {
+                items: Some(Items::Tuple(ty.elems.iter().map(Shape::of).collect())),
+                ..Default::default()
+            },
+            // `Date` is sent as a string by `toJSON`.
+            Type::Object(ty) if ty.builtin.is_some() => Shape::default(),
+            // Methods of classes are not members, so instances of classes are
+            // copied like objects.
+            Type::Object(ty) => Shape {
+                props: Some(
+                    ty.members
+                        .iter()
+                        .map(|m| match m {
+                            TypeElement::Property(p) => (p.name.clone(), Shape::of(&p.ty)),
+                        })
+                        .collect(),
+                ),
+                index: ty
+                    .index_signature
+                    .as_ref()
+                    .map(|sig| box Shape::of(&sig.ty)),
+                items: None,
+            },
+            Type::Intersection(ty) => ty
+                .types
+                .iter()
+                .map(Shape::of)
+                .fold(Shape::default(), Shape::merge),
+            Type::Union(ty) => ty
+                .types
+                .iter()
+                .map(|ty| Shape::of(ty))
+                .fold(Shape::default(), Shape::merge),
+        }
+    }
+
+    fn merge(self, other: Shape) -> Shape {
+        let props = match (self.props, other.props) {
+            (Some(mut props), Some(other)) => {
+                for (name, shape) in other {
+                    match props.iter_mut().find(|(n, _)| *n == name) {
+                        Some((_, prev)) => *prev = take(prev).merge(shape),
+                        None => props.push((name, shape)),
+                    }
+                }
+                Some(props)
+            }
+            (props, None) | (None, props) => props,
+        };
+
+        let index = match (self.index, other.index) {
+            (Some(a), Some(b)) => Some(box a.merge(*b)),
+            (index, None) | (None, index) => index,
+        };
+
+        let items = match (self.items, other.items) {
+            (Some(Items::Array(a)), Some(Items::Array(b))) => Some(Items::Array(box a.merge(*b))),
+            (Some(Items::Tuple(a)), Some(Items::Tuple(b))) if a.len() == b.len() => Some(
+                Items::Tuple(a.into_iter().zip(b).map(|(a, b)| a.merge(b)).collect()),
+            ),
+            // `string[] | [number, boolean]` keeps the elements declared by any
+            // of them.
+            (Some(a), Some(b)) => Some(Items::Array(
+                box a
+                    .into_elems()
+                    .into_iter()
+                    .chain(b.into_elems())
+                    .fold(Shape::default(), Shape::merge),
+            )),
+            (items, None) | (None, items) => items,
+        };
+
+        Shape {
+            props,
+            index,
+            items,
+        }
+    }
+
+    /// Returns true if a value of this shape may have undeclared fields.
+    fn needs_projection(&self) -> bool {
+        self.object_needs_projection()
+            || match &self.items {
+                Some(Items::Array(elem)) => elem.needs_projection(),
+                // Elements after the last declared one are removed.
+                Some(Items::Tuple(..)) => true,
+                None => false,
+            }
+    }
+
+    /// Returns true if an object of this shape may have undeclared fields.
+    fn object_needs_projection(&self) -> bool {
+        match (&self.props, &self.index) {
+            (None, _) => false,
+            // Every key is declared by the index signature.
+            (Some(props), Some(index)) => {
+                index.needs_projection() || props.iter().any(|(_, p)| p.needs_projection())
+            }
+            (Some(_), None) => true,
+        }
+    }
+
+    /// Creates an expression which copies declared fields of `value`.
+    ///
+    /// `value` is evaluated multiple times, so it should not have side
+    /// effects. `depth` is the number of enclosing `map` callbacks.
+    fn project(&self, value: Box<Expr>, depth: usize) -> Box<Expr> {
+        if !self.needs_projection() {
+            return value;
+        }
+
+        let mut expr = value.clone();
+
+        // `v !== null && typeof v === "object" ? { id: v.id } : v`
+        if let Some(props) = self
+            .props
+            .as_ref()
+            .filter(|_| self.object_needs_projection())
+        {
+            let test = box Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                op: op!("&&"),
+                left: box Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    op: op!("!=="),
+                    left: value.clone(),
+                    right: box Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+                }),
+                right: box Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    op: op!("==="),
+                    left: box Expr::Unary(UnaryExpr {
+                        span: DUMMY_SP,
+                        op: op!("typeof"),
+                        arg: value.clone(),
+                    }),
+                    right: box Expr::from("object"),
+                }),
+            });
+            let object = box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: self
+                    .index
+                    .as_ref()
+                    .map(|index| {
+                        PropOrSpread::Spread(SpreadElement {
+                            dot3_token: DUMMY_SP,
+                            expr: project_entries(value.clone(), index, depth),
+                        })
+                    })
+                    .into_iter()
+                    .chain(props.iter().map(|(name, shape)| {
+                        let (key, prop) = prop_key(name);
+                        let value = box Expr::Member(MemberExpr {
+                            span: DUMMY_SP,
+                            obj: value.clone(),
+                            prop,
+                        });
+
+                        PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                            key,
+                            value: shape.project(value, depth),
+                        }))
+                    }))
+                    .collect(),
+            });
+
+            expr = box Expr::Cond(CondExpr {
+                span: DUMMY_SP,
+                test,
+                cons: object,
+                alt: expr,
+            });
+        }
+
+        // `Array.isArray(v) ? v.map((v1)=>...) : v`
+        if let Some(items) = &self.items {
+            let array = match items {
+                Items::Array(elem) if !elem.needs_projection() => value.clone(),
+                Items::Array(elem) => {
+                    let item = private_ident!(format!("v{}", depth + 1));
+                    let callback = ArrowExpr {
+                        span: DUMMY_SP,
+                        params: vec![Pat::Ident(item.clone().into())],
+                        body: BlockStmtOrExpr::Expr(elem.project(box item.into(), depth + 1)),
+                        is_async: false,
+                        is_generator: false,
+                        type_params: Default::default(),
+                        return_type: Default::default(),
+                    };
+
+                    box Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: value.clone().make_member(quote_ident!("map")).as_callee(),
+                        args: vec![Expr::Arrow(callback).as_arg()],
+                        type_args: Default::default(),
+                    })
+                }
+                Items::Tuple(elems) => box Expr::Array(ArrayLit {
+                    span: DUMMY_SP,
+                    elems: elems
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, shape)| {
+                            let value = box Expr::Member(MemberExpr {
+                                span: DUMMY_SP,
+                                obj: value.clone(),
+                                prop: MemberProp::Computed(ComputedPropName {
+                                    span: DUMMY_SP,
+                                    expr: idx.into(),
+                                }),
+                            });
+                            Some(shape.project(value, depth).as_arg())
+                        })
+                        .collect(),
+                }),
+            };
+
+            expr = box Expr::Cond(CondExpr {
+                span: DUMMY_SP,
+                test: box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: member_expr!(DUMMY_SP, Array.isArray).as_callee(),
+                    args: vec![value.as_arg()],
+                    type_args: Default::default(),
+                }),
+                cons: array,
+                alt: expr,
+            });
+        }
+
+        expr
+    }
+}
+
+/// Creates `Object.fromEntries(Object.entries(v).map(([k, v1])=>[k, ...]))`,
+/// which copies the entries of an index signature, or `v` if the values of
+/// `index` are sent as-is.
+fn project_entries(value: Box<Expr>, index: &Shape, depth: usize) -> Box<Expr> {
+    if !index.needs_projection() {
+        return value;
+    }
+
+    let key = private_ident!("k");
+    let item = private_ident!(format!("v{}", depth + 1));
+    let callback = ArrowExpr {
+        span: DUMMY_SP,
+        params: vec![Pat::Array(ArrayPat {
+            span: DUMMY_SP,
+            elems: vec![
+                Some(Pat::Ident(key.clone().into())),
+                Some(Pat::Ident(item.clone().into())),
+            ],
+            optional: false,
+            type_ann: Default::default(),
+        })],
+        body: BlockStmtOrExpr::Expr(box Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: vec![
+                Some(key.as_arg()),
+                Some(index.project(box item.into(), depth + 1).as_arg()),
+            ],
+        })),
+        is_async: false,
+        is_generator: false,
+        type_params: Default::default(),
+        return_type: Default::default(),
+    };
+
+    let entries = box Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: member_expr!(DUMMY_SP, Object.entries).as_callee(),
+        args: vec![value.as_arg()],
+        type_args: Default::default(),
+    });
+
+    box Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: member_expr!(DUMMY_SP, Object.fromEntries).as_callee(),
+        args: vec![CallExpr {
+            span: DUMMY_SP,
+            callee: entries.make_member(quote_ident!("map")).as_callee(),
+            args: vec![Expr::Arrow(callback).as_arg()],
+            type_args: Default::default(),
+        }
+        .as_arg()],
+        type_args: Default::default(),
+    })
+}
+
+impl Items {
+    /// Shapes of all elements.
+    fn into_elems(self) -> Vec<Shape> {
+        match self {
+            Items::Array(elem) => vec![*elem],
+            Items::Tuple(elems) => elems,
+        }
+    }
+}
+
+/// Creates the key of a copied property and the member to read it, like
+/// `id: v.id` or `"created-at": v["created-at"]`.
+fn prop_key(name: &str) -> (PropName, MemberProp) {
+    if Ident::verify_symbol(name).is_ok() {
+        (
+            PropName::Ident(quote_ident!(name)),
+            MemberProp::Ident(quote_ident!(name)),
+        )
+    } else {
+        let s = Str {
+            span: DUMMY_SP,
+            value: name.into(),
+            raw: None,
+        };
+        (
+            PropName::Str(s.clone()),
+            MemberProp::Computed(ComputedPropName {
+                span: DUMMY_SP,
+                expr: box Expr::Lit(Lit::Str(s)),
+            }),
+        )
+    }
+}
@@ -0,0 +1,160 @@
+//! Static analysis of `@Validate`.
+
+use fnapi_api_def::Validation;
+use swc_common::{errors::HANDLER, Span, Spanned};
+use swc_ecmascript::ast::*;
+
+use super::{import_analyzer::ImportMap, params::param_name, FNAPI_API_MODULE};
+
+/// Resolves `@Validate("email", [validators.email])` against `params` by
+/// name, and returns the validations of each parameter.
+///
+/// Returns [None] if there's an error.
+pub(super) fn parse_validations(
+    imports: &ImportMap,
+    decorators: &[(Span, Vec<ExprOrSpread>)],
+    params: &[Param],
+) -> Option<Vec<Vec<Validation>>> {
+    let mut validations = vec![vec![]; params.len()];
+    let mut has_error = false;
+
+    for (span, args) in decorators {
+        let (field, rules) = match &**args {
+            [ExprOrSpread {
+                spread: None,
+                expr: box Expr::Lit(Lit::Str(field)),
+            }, ExprOrSpread {
+                spread: None,
+                expr: box Expr::Array(rules),
+            }] => (field, rules),
+            _ => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(
+                            *span,
+                            "@Validate takes a parameter name and an array of validators",
+                        )
+                        .help("Use it like '@Validate(\"email\", [validators.email])'")
+                        .emit();
+                });
+                has_error = true;
+                continue;
+            }
+        };
+
+        let idx = params
+            .iter()
+            .position(|param| param_name(param).map_or(false, |i| i.sym == field.value));
+        let idx = match idx {
+            Some(idx) => idx,
+            None => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(
+                            field.span,
+                            &format!("'{}' does not match any parameter", field.value),
+                        )
+                        .help("Validators are bound to parameters with the same name")
+                        .emit();
+                });
+                has_error = true;
+                continue;
+            }
+        };
+
+        for rule in rules.elems.iter().flatten() {
+            match parse_validator(imports, &rule.expr) {
+                Ok(Some(v)) => {
+                    if !validations[idx].contains(&v) {
+                        validations[idx].push(v);
+                    }
+                }
+                Ok(None) => {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_warn(
+                                rule.span(),
+                                "This validator is not known to the compiler, so it's ignored",
+                            )
+                            .help(
+                                "Use validators.required, validators.email, \
+                                 validators.minLength(n) or validators.maxLength(n)",
+                            )
+                            .emit();
+                    });
+                }
+                Err(()) => has_error = true,
+            }
+        }
+    }
+
+    if has_error {
+        return None;
+    }
+
+    Some(validations)
+}
+
+/// Returns `Ok(None)` for unknown validators.
+fn parse_validator(imports: &ImportMap, e: &Expr) -> Result<Option<Validation>, ()> {
+    match e {
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if imports.is_import(obj, FNAPI_API_MODULE, "validators") => match &*prop.sym {
+            "required" => Ok(Some(Validation::Required)),
+            "email" => Ok(Some(Validation::Email)),
+            "minLength" | "maxLength" => {
+                HANDLER.with(|handler| {
+                    handler
+                        .struct_span_err(
+                            e.span(),
+                            &format!("validators.{} takes a length", prop.sym),
+                        )
+                        .help(&format!("Use it like 'validators.{}(8)'", prop.sym))
+                        .emit();
+                });
+                Err(())
+            }
+            _ => Ok(None),
+        },
+
+        Expr::Call(CallExpr {
+            callee:
+                Callee::Expr(box Expr::Member(MemberExpr {
+                    obj,
+                    prop: MemberProp::Ident(prop),
+                    ..
+                })),
+            args,
+            ..
+        }) if imports.is_import(obj, FNAPI_API_MODULE, "validators") => {
+            let ctor = match &*prop.sym {
+                "minLength" => |length| Validation::MinLength { length },
+                "maxLength" => |length| Validation::MaxLength { length },
+                _ => return Ok(None),
+            };
+
+            match &**args {
+                [ExprOrSpread {
+                    spread: None,
+                    expr: box Expr::Lit(Lit::Num(n)),
+                }] if n.value >= 0.0 && n.value.fract() == 0.0 => Ok(Some(ctor(n.value as u32))),
+                _ => {
+                    HANDLER.with(|handler| {
+                        handler
+                            .struct_span_err(
+                                e.span(),
+                                &format!("validators.{} takes a non-negative integer", prop.sym),
+                            )
+                            .emit();
+                    });
+                    Err(())
+                }
+            }
+        }
+
+        _ => Ok(None),
+    }
+}
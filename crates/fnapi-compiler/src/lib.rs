@@ -2,7 +2,7 @@
 #![feature(box_syntax)]
 #![feature(never_type)]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
@@ -11,6 +11,10 @@ pub mod project;
 pub mod target;
 mod type_server;
 
+/// Identifies the build of the compiler. Outputs of different builds may
+/// differ, even if the version is the same.
+pub const BUILD_ID: &str = env!("FNAPI_COMPILER_BUILD_ID");
+
 /// One input file.
 pub struct ServerApiFile {
     path: PathBuf,
@@ -20,4 +24,37 @@ impl ServerApiFile {
     pub fn from_file(path: PathBuf) -> Result<Self> {
         Ok(Self { path })
     }
+
+    /// Returns the name used for routes of exported api functions in `path`,
+    /// which is the file stem in camelCase. e.g. `user-auth.ts` becomes
+    /// `userAuth`.
+    pub fn function_group_name(path: &Path) -> String {
+        let stem = path
+            .file_stem()
+            .map(|v| v.to_string_lossy())
+            .unwrap_or_default();
+
+        let mut name = String::new();
+        let mut upper = false;
+        for c in stem.chars() {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '$' {
+                if name.is_empty() && c.is_ascii_digit() {
+                    name.push('_');
+                }
+                if upper && !name.is_empty() {
+                    name.push(c.to_ascii_uppercase());
+                } else {
+                    name.push(c);
+                }
+                upper = false;
+            } else {
+                upper = true;
+            }
+        }
+
+        if name.is_empty() {
+            name.push_str("api");
+        }
+        name
+    }
 }
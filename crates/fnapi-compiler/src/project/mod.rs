@@ -1,4 +1,8 @@
-use std::{path::PathBuf, process::Stdio, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+};
 
 use anyhow::{bail, Context, Result};
 use fnapi_core::Env;
@@ -52,6 +56,9 @@ impl InputFiles {
 #[derive(Debug, Clone)]
 pub struct ProjectConfig {
     pub input: Arc<InputFiles>,
+
+    /// Prefix for the path of all routes, like `/api`.
+    pub route_prefix: Option<String>,
 }
 
 impl ProjectConfig {
@@ -72,6 +79,7 @@ impl ProjectConfig {
             type_server,
             files,
             server_target,
+            route_prefix: self.route_prefix.clone(),
         }))
     }
 }
@@ -89,4 +97,54 @@ pub struct Project {
     pub files: Arc<Vec<PathBuf>>,
 
     pub server_target: Arc<dyn ServerTarget>,
+
+    pub route_prefix: Option<String>,
+}
+
+impl Project {
+    /// Returns files which `file` depends on, transitively.
+    pub async fn dependencies_of(&self, file: &Path) -> Result<Vec<PathBuf>> {
+        self.type_server
+            .query_dependencies(&file.display().to_string())
+            .await
+            .with_context(|| format!("failed to query dependencies of `{}`", file.display()))
+    }
+
+    /// Returns files which affect types of all files, like `tsconfig.json`
+    /// and global declarations.
+    pub async fn config_files(&self) -> Result<Vec<PathBuf>> {
+        self.type_server
+            .query_config_files()
+            .await
+            .context("failed to query config files")
+    }
+
+    /// Uses `text` as the content of `file` instead of the file on the disk,
+    /// until `file` is invalidated. Used for unsaved documents.
+    pub async fn update(&self, env: &Env, file: &Path, text: &str) -> Result<()> {
+        env.with(|| self.modules.update(file, text.to_string()))?;
+
+        self.type_server
+            .update_file(&file.display().to_string(), text)
+            .await
+            .context("failed to update a file of the type server")
+    }
+
+    /// Forgets the cached state of `files`, so they are read again from the
+    /// disk on the next compilation.
+    pub async fn invalidate(&self, files: &[PathBuf]) -> Result<()> {
+        for file in files {
+            self.modules.invalidate(file);
+        }
+
+        self.type_server
+            .refresh_files(
+                &files
+                    .iter()
+                    .map(|v| v.display().to_string())
+                    .collect::<Vec<_>>(),
+            )
+            .await
+            .context("failed to refresh files of the type server")
+    }
 }
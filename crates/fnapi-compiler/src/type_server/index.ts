@@ -20,11 +20,11 @@ async function start() {
         methodMapping: methods,
     };
 
-    console.log("Starting Server");
+    console.error("Starting Server");
     const s = new Server(serverOptions);
 
     s.start();
-    console.log('Started');
+    console.error('Started');
 }
 start();
 
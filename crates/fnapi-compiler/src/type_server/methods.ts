@@ -1,6 +1,7 @@
 import { OpenrpcDocument } from "@open-rpc/meta-schema";
 import { MethodMapping } from "@open-rpc/server-js/build/router";
-import { Project, ClassDeclaration, ts, Type, Symbol } from "ts-morph";
+import { Project, ClassDeclaration, Signature, SourceFile, ts, Type, Symbol } from "ts-morph";
+import { dirname, resolve } from "path";
 
 const project = process.env.TS_CONFIG_PATH ? new Project({
     tsConfigFilePath: process.env.TS_CONFIG_PATH,
@@ -43,6 +44,13 @@ interface SKeywordType extends BaseSType {
 interface SObjectType extends BaseSType {
     kind: 'object'
     members: SProperty[]
+    indexSignature?: SIndexSignature
+    builtin?: string
+}
+
+interface SIndexSignature {
+    key: 'string' | 'number'
+    type: SType
 }
 
 type STypeElement = SProperty;
@@ -52,6 +60,8 @@ interface SProperty {
     name: string
     type: SType
     optional: boolean
+    description?: string
+    examples?: string[]
 }
 
 /**
@@ -62,14 +72,50 @@ type SType = SUnionType | SIntersectionType | SArrayType | STupleType | SKeyword
 function serializeProperty(s: Symbol): SProperty {
     const node = s.getValueDeclarationOrThrow();
 
+    // JSDoc of the property, like `/** Title of the todo. @example "Buy milk" */`
+    const description = ts.displayPartsToString(
+        s.compilerSymbol.getDocumentationComment(project.getTypeChecker().compilerObject)
+    ).trim();
+    const examples = s.getJsDocTags()
+        .filter(tag => tag.getName() === 'example')
+        .map(tag => ts.displayPartsToString(tag.getText()).trim());
+
     return {
         kind: 'property',
         name: s.getName(),
         type: serializeType(s.getTypeAtLocation(node)),
         optional: s.hasFlags(ts.SymbolFlags.Optional),
+        description: description || undefined,
+        examples,
     }
 }
 
+/**
+ * Builtin classes whose instances are sent as-is, like `Date` which is sent
+ * as a string by `toJSON`.
+ */
+const BUILTIN_CLASSES = new Set(['Date']);
+
+/**
+ * Returns the name of `t` if it's a builtin class of `BUILTIN_CLASSES`, and
+ * not a class of the project with the same name.
+ */
+function builtinClassName(t: Type): string | undefined {
+    const symbol = t.getSymbol();
+    if (!symbol || !BUILTIN_CLASSES.has(symbol.getName())) {
+        return undefined;
+    }
+
+    const program = project.getProgram().compilerObject;
+    const isLib = symbol.getDeclarations()
+        .every((d) => program.isSourceFileDefaultLibrary(d.getSourceFile().compilerNode));
+    return isLib ? symbol.getName() : undefined;
+}
+
+function isMethod(s: Symbol): boolean {
+    const node = s.getValueDeclaration();
+    return !!node && s.getTypeAtLocation(node).getCallSignatures().length > 0;
+}
 
 function serializeType(t: Type): SType {
 
@@ -137,36 +183,64 @@ function serializeType(t: Type): SType {
     }
 
     if (t.isObject()) {
+        const builtin = builtinClassName(t);
+        if (builtin) {
+            return {
+                kind: 'object',
+                members: [],
+                builtin,
+            }
+        }
+
+        // Methods, like `toString` of a class, are not sent.
+        const members = t.getApparentProperties().filter((p) => !isMethod(p));
+        const stringIndexType = t.getStringIndexType();
+        const numberIndexType = t.getNumberIndexType();
+        const indexSignature: SIndexSignature | undefined = stringIndexType
+            ? { key: 'string', type: serializeType(stringIndexType) }
+            : numberIndexType
+                ? { key: 'number', type: serializeType(numberIndexType) }
+                : undefined;
+
         return {
             kind: 'object',
-            members: t.getApparentProperties().map(serializeProperty)
+            members: members.map(serializeProperty),
+            indexSignature,
         }
     }
 
 
-    console.log(`Unhandled type: ${t.getText()}`);
+    console.error(`Unhandled type: ${t.getText()}`);
     return `Unhandled type: ${t.getText()}` as any;
 }
 
 export const methods: MethodMapping = {
-    queryTypesOfMethod: async (filename: string, methodName: string) => {
+    queryTypesOfMethod: async (filename: string, methodName: string, className: string | null) => {
         const sf = project.getSourceFileOrThrow(filename);
-        // console.log(sf);
-        const defaultExport = sf.getStatementOrThrow(
-            s => ts.isClassDeclaration(s.compilerNode) &&
-                !!s.compilerNode.modifiers &&
-                s.compilerNode.modifiers.some(m => m.kind === ts.SyntaxKind.DefaultKeyword) &&
-                s.compilerNode.modifiers.some(m => m.kind === ts.SyntaxKind.ExportKeyword)
-        ) as ClassDeclaration;
 
-        const method = defaultExport.getMethods().find(m => m.getName() === methodName);
-        if (!method) {
-            throw new Error(`Method ${methodName} not found in ${filename}`);
+        let signature: Signature;
+        if (className) {
+            const cls: ClassDeclaration = sf.getClassOrThrow(className);
+
+            const method = cls.getMethods().find(m => m.getName() === methodName);
+            if (!method) {
+                throw new Error(`Method ${className}.${methodName} not found in ${filename}`);
+            }
+            signature = method.getSignature();
+        } else {
+            // `export async function f()` or `export const f = FnApi(...)`
+            const decl = sf.getExportedDeclarations().get(methodName)?.[0];
+            if (!decl) {
+                throw new Error(`Function ${methodName} not found in ${filename}`);
+            }
+            signature = decl.getType().getCallSignatures()[0];
+            if (!signature) {
+                throw new Error(`${methodName} in ${filename} is not a function`);
+            }
         }
-        const signature = method.getSignature();
 
         const params = signature.getParameters().map((p) => serializeProperty(p).type);
-
+        const paramNames = signature.getParameters().map((p) => p.getName());
 
         const returnType = signature.getReturnType();
 
@@ -175,10 +249,77 @@ export const methods: MethodMapping = {
 
         return JSON.stringify({
             params,
+            paramNames,
             returnType: serializeType(actualReturnType)
         })
     },
 
+    queryDependencies: async (filename: string) => {
+        const deps = new Set<string>();
+
+        const visit = (sf: SourceFile) => {
+            for (const dep of sf.getReferencedSourceFiles()) {
+                const path = dep.getFilePath();
+                if (deps.has(path)) {
+                    continue;
+                }
+                deps.add(path);
+
+                // Packages are tracked by their entry files.
+                if (!dep.isInNodeModules()) {
+                    visit(dep);
+                }
+            }
+        };
+        visit(project.getSourceFileOrThrow(filename));
+
+        return JSON.stringify([...deps]);
+    },
+
+    queryConfigFiles: async () => {
+        const files = new Set<string>();
+
+        // `tsconfig.json` and the configs it extends.
+        const tsConfigPath = process.env.TS_CONFIG_PATH;
+        if (tsConfigPath) {
+            const configFile = ts.readJsonConfigFile(tsConfigPath, ts.sys.readFile);
+            ts.parseJsonSourceFileConfigFileContent(configFile, ts.sys, dirname(tsConfigPath));
+
+            files.add(resolve(tsConfigPath));
+            for (const extended of configFile.extendedSourceFiles ?? []) {
+                files.add(extended);
+            }
+        }
+
+        // Global declarations, like `lib.dom.d.ts` or `@types/node`.
+        for (const sf of project.getProgram().compilerObject.getSourceFiles()) {
+            if (sf.isDeclarationFile) {
+                files.add(sf.fileName);
+            }
+        }
+
+        return JSON.stringify([...files]);
+    },
+
+    refreshFiles: async (filenames: string[]) => {
+        for (const filename of filenames) {
+            const sf = project.getSourceFile(filename);
+            if (sf) {
+                sf.refreshFromFileSystemSync();
+            } else {
+                project.addSourceFileAtPath(filename);
+            }
+        }
+
+        return '';
+    },
+
+    updateFile: async (filename: string, text: string) => {
+        project.createSourceFile(filename, text, { overwrite: true });
+
+        return '';
+    },
+
     checkStarted: async () => '',
 };
 
@@ -195,6 +336,42 @@ export const openrpcDocument = {
             params: [
                 { name: "filename", schema: { type: "string" } },
                 { name: "methodName", schema: { type: "string" } },
+                { name: "className", schema: { type: ["string", "null"] } },
+            ],
+            result: {
+                name: "j", schema: { type: "string" }
+            }
+        },
+        {
+            name: "queryDependencies",
+            params: [
+                { name: "filename", schema: { type: "string" } },
+            ],
+            result: {
+                name: "j", schema: { type: "string" }
+            }
+        },
+        {
+            name: "queryConfigFiles",
+            params: [],
+            result: {
+                name: "j", schema: { type: "string" }
+            }
+        },
+        {
+            name: "refreshFiles",
+            params: [
+                { name: "filenames", schema: { type: "array", items: { type: "string" } } },
+            ],
+            result: {
+                name: "j", schema: { type: "string" }
+            }
+        },
+        {
+            name: "updateFile",
+            params: [
+                { name: "filename", schema: { type: "string" } },
+                { name: "text", schema: { type: "string" } },
             ],
             result: {
                 name: "j", schema: { type: "string" }
@@ -1,5 +1,6 @@
 use std::{
     io::Write,
+    path::PathBuf,
     process::{Child, Command, Stdio},
     sync::Arc,
     time::Duration,
@@ -33,6 +34,9 @@ impl TypeServer {
         cmd.arg("-");
 
         cmd.stdin(Stdio::piped());
+        // Stdout of fnapi is used by `fnapi lsp` and machine-readable
+        // diagnostics, so the type server logs to stderr.
+        cmd.stdout(Stdio::null());
 
         let port = thread_rng().gen_range::<u16, _>(40000..60000);
         cmd.env("PORT", port.to_string());
@@ -98,21 +102,25 @@ impl TypeServer {
         Ok(())
     }
 
+    /// Queries types of a static method of `class_name`, or an exported
+    /// function if `class_name` is [None].
     #[tracing::instrument(name = "TypeServer::query_types_of_method", skip(self, filename))]
     pub async fn query_types_of_method(
         &self,
         filename: &str,
+        class_name: Option<&str>,
         method_name: &str,
     ) -> Result<MethodTypes> {
-        debug!("Sending query for `{}`", method_name);
+        debug!("Sending query for `{:?}.{}`", class_name, method_name);
 
         let filename_arg = Value::String(filename.into());
         let name_arg = Value::String(method_name.into());
+        let class_name_arg = class_name.map_or(Value::Null, |v| Value::String(v.into()));
         let res = self
             .client
             .call_method(
                 "queryTypesOfMethod",
-                Params::Array(vec![filename_arg, name_arg]),
+                Params::Array(vec![filename_arg, name_arg, class_name_arg]),
             )
             .await
             .map_err(|e| anyhow!("rpc failed: {}", e))?;
@@ -127,14 +135,90 @@ impl TypeServer {
         Ok(body)
     }
 
+    /// Queries files which `filename` depends on, transitively.
+    #[tracing::instrument(name = "TypeServer::query_dependencies", skip_all)]
+    pub async fn query_dependencies(&self, filename: &str) -> Result<Vec<PathBuf>> {
+        let res = self
+            .client
+            .call_method(
+                "queryDependencies",
+                Params::Array(vec![Value::String(filename.into())]),
+            )
+            .await
+            .map_err(|e| anyhow!("rpc failed: {}", e))?;
+
+        let s = res.as_str().unwrap();
+
+        serde_json::from_str(s).with_context(|| format!("failed to deserialize json: {}", s))
+    }
+
+    /// Queries files which affect types of all files, like `tsconfig.json`,
+    /// configs extended by it and global declarations.
+    #[tracing::instrument(name = "TypeServer::query_config_files", skip_all)]
+    pub async fn query_config_files(&self) -> Result<Vec<PathBuf>> {
+        let res = self
+            .client
+            .call_method("queryConfigFiles", Params::None)
+            .await
+            .map_err(|e| anyhow!("rpc failed: {}", e))?;
+
+        let s = res.as_str().unwrap();
+
+        serde_json::from_str(s).with_context(|| format!("failed to deserialize json: {}", s))
+    }
+
+    /// Makes the type server read `filenames` again from the disk.
+    ///
+    /// Files which are not known to the type server are added to the project.
+    #[tracing::instrument(name = "TypeServer::refresh_files", skip_all)]
+    pub async fn refresh_files(&self, filenames: &[String]) -> Result<()> {
+        let filenames_arg = Value::Array(
+            filenames
+                .iter()
+                .map(|filename| Value::String(filename.clone()))
+                .collect(),
+        );
+
+        let _res = self
+            .client
+            .call_method("refreshFiles", Params::Array(vec![filenames_arg]))
+            .await
+            .map_err(|e| anyhow!("rpc failed: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Makes the type server use `text` as the content of `filename`, instead
+    /// of the file on the disk.
+    #[tracing::instrument(name = "TypeServer::update_file", skip_all)]
+    pub async fn update_file(&self, filename: &str, text: &str) -> Result<()> {
+        let _res = self
+            .client
+            .call_method(
+                "updateFile",
+                Params::Array(vec![
+                    Value::String(filename.to_string()),
+                    Value::String(text.to_string()),
+                ]),
+            )
+            .await
+            .map_err(|e| anyhow!("rpc failed: {}", e))?;
+
+        Ok(())
+    }
+
     pub fn query_return_type_of_method_sync(
         &self,
         filename: &str,
+        class_name: Option<&str>,
         method_name: &str,
     ) -> Result<MethodTypes> {
         let rt = Handle::current();
 
-        rt.block_on(async { self.query_types_of_method(filename, method_name).await })
+        rt.block_on(async {
+            self.query_types_of_method(filename, class_name, method_name)
+                .await
+        })
     }
 }
 
@@ -147,10 +231,14 @@ impl Drop for TypeServer {
     }
 }
 
-#[derive(Debug, PartialEq, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MethodTypes {
     pub params: Vec<Type>,
 
+    /// Names of the parameters. Destructured parameters are named like `__0`.
+    #[serde(default)]
+    pub param_names: Vec<String>,
+
     pub return_type: Type,
 }
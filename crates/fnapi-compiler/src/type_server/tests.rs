@@ -34,7 +34,7 @@ mod valid {
                 let ts = start().await;
 
                 let res = ts
-                    .query_types_of_method(&fname("simple.ts"), "foo")
+                    .query_types_of_method(&fname("simple.ts"), Some("TestApi"), "foo")
                     .await
                     .unwrap();
                 dbg!(&res);
@@ -43,6 +43,7 @@ mod valid {
                     res,
                     MethodTypes {
                         params: Default::default(),
+                        param_names: Default::default(),
                         return_type: Type::Keyword(KeywordType {
                             keyword: swc_ecmascript::ast::TsKeywordTypeKind::TsStringKeyword
                         })
@@ -54,4 +55,70 @@ mod valid {
         )
         .unwrap();
     }
+
+    #[test]
+    fn update_file() {
+        run_async_test(
+            HandlerOpts {
+                color: ColorConfig::Always,
+            },
+            |_cm| async move {
+                let ts = start().await;
+
+                ts
+                    .update_file(
+                        &fname("simple.ts"),
+                        "export default class TestApi {\n    static async foo(): Promise<number> \
+                         {\n    }\n}\n",
+                    )
+                    .await
+                    .unwrap();
+
+                let res = ts
+                    .query_types_of_method(&fname("simple.ts"), Some("TestApi"), "foo")
+                    .await
+                    .unwrap();
+
+                assert_eq!(
+                    res.return_type,
+                    Type::Keyword(KeywordType {
+                        keyword: swc_ecmascript::ast::TsKeywordTypeKind::TsNumberKeyword
+                    })
+                );
+
+                Ok(())
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn query_config_files() {
+        run_async_test(
+            HandlerOpts {
+                color: ColorConfig::Always,
+            },
+            |_cm| async move {
+                let ts = TypeServer::start(&InputFiles::TsConfig(
+                    "tests/projects/basic/1/tsconfig.json".into(),
+                ))
+                .await
+                .unwrap();
+
+                let files = ts.query_config_files().await.unwrap();
+                dbg!(&files);
+
+                assert!(files
+                    .iter()
+                    .any(|f| f.ends_with("tests/projects/basic/1/tsconfig.json")));
+                // `lib.es2016.d.ts` of the target
+                assert!(files.iter().any(|f| f
+                    .file_name()
+                    .map_or(false, |name| name.to_string_lossy().starts_with("lib."))));
+
+                Ok(())
+            },
+        )
+        .unwrap();
+    }
 }
@@ -0,0 +1,15 @@
+#![deny(warnings)]
+
+use std::path::PathBuf;
+
+use fnapi_compiler::ServerApiFile;
+
+#[testing::fixture("tests/discovery/api/*.ts")]
+fn api_file(input: PathBuf) {
+    assert!(ServerApiFile::is_api_file(&input).unwrap());
+}
+
+#[testing::fixture("tests/discovery/not-api/*.ts")]
+fn not_api_file(input: PathBuf) {
+    assert!(!ServerApiFile::is_api_file(&input).unwrap());
+}
@@ -0,0 +1,4 @@
+/** @fnapi */
+export async function login(email: string): Promise<string> {
+    return email;
+}
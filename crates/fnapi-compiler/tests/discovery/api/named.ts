@@ -0,0 +1,8 @@
+import { FnApi } from "@fnapi/api";
+
+export default class Api {
+  @FnApi()
+  static async hello(name: string): Promise<string> {
+    return `Hello, ${name}`;
+  }
+}
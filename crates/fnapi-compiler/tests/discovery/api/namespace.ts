@@ -0,0 +1,6 @@
+import * as fnapi from "@fnapi/api";
+
+export default class Namespace {
+  @fnapi.FnApi()
+  static async hello(): Promise<void> {}
+}
@@ -0,0 +1,6 @@
+import { FnApi as Api } from "@fnapi/api";
+
+export default class Renamed {
+  @Api()
+  static async hello(): Promise<void> {}
+}
@@ -0,0 +1,7 @@
+import { Context } from "@fnapi/api";
+
+export function currentUser() {
+  return Context.get(UserProvider);
+}
+
+const UserProvider = {};
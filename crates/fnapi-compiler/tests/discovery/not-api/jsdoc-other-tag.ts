@@ -0,0 +1,6 @@
+/**
+ * See @fnapi/api for apis.
+ */
+export function format(email: string): string {
+    return email;
+}
@@ -0,0 +1,6 @@
+import { FnApi } from "./local";
+
+export default class NotApi {
+  @FnApi()
+  static async hello(): Promise<void> {}
+}
@@ -0,0 +1,8 @@
+import { FnApi } from '@fnapi/api';
+
+export default class TodoApi {
+    @FnApi({ httpMethod: "GET" })
+    static async search(keyword: string): Promise<string> {
+        return keyword;
+    }
+}
@@ -0,0 +1,9 @@
+import { FnApi, Http } from '@fnapi/api';
+
+export default class TodoApi {
+    @FnApi()
+    @Http({ method: "GET", path: "/todos" })
+    static async search(keyword: string): Promise<string> {
+        return keyword;
+    }
+}
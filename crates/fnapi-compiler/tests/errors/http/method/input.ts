@@ -0,0 +1,9 @@
+import { FnApi, Http } from '@fnapi/api';
+
+export default class TodoApi {
+    @FnApi()
+    @Http({ method: "FETCH" })
+    static async get(id: string): Promise<string> {
+        return id;
+    }
+}
@@ -0,0 +1,9 @@
+import { FnApi, Http } from '@fnapi/api';
+
+export default class TodoApi {
+    @FnApi()
+    @Http({ method: "GET", path: "/todos/:id" })
+    static async get(id?: string): Promise<string> {
+        return id;
+    }
+}
@@ -0,0 +1,9 @@
+import { FnApi, Http } from '@fnapi/api';
+
+export default class TodoApi {
+    @FnApi()
+    @Http({ method: "PUT", path: "/todos/:todoId" })
+    static async update(id: string): Promise<string> {
+        return id;
+    }
+}
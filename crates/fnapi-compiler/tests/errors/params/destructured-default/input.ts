@@ -0,0 +1,8 @@
+import { FnApi } from '@fnapi/api';
+
+export default class TodoApi {
+    @FnApi()
+    static async search({ limit } = { limit: 10 }): Promise<string> {
+        return `${limit}`;
+    }
+}
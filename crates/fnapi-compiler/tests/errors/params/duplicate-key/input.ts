@@ -0,0 +1,8 @@
+import { FnApi } from '@fnapi/api';
+
+export default class TodoApi {
+    @FnApi()
+    static async rename(p1: string, [first, last]: [string, string]): Promise<string> {
+        return p1 + first + last;
+    }
+}
@@ -0,0 +1,8 @@
+import { FnApi } from '@fnapi/api';
+
+export default class TodoApi {
+    @FnApi()
+    static async create({ title, ...rest }: { title: string; done: boolean }): Promise<string> {
+        return title;
+    }
+}
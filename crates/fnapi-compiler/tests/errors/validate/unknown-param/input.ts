@@ -0,0 +1,9 @@
+import { FnApi, Validate, validators } from '@fnapi/api';
+
+export default class AuthApi {
+    @FnApi()
+    @Validate("mail", [validators.email])
+    static async login(email: string): Promise<string> {
+        return email;
+    }
+}
@@ -0,0 +1,5 @@
+import { FnApi } from '@fnapi/api';
+
+export const whoami = FnApi(async (): Promise<string> => {
+    return this.name;
+});
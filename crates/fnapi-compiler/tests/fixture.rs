@@ -10,7 +10,11 @@ use fnapi_compiler::{
 };
 use fnapi_core::Env;
 use fnapi_testing::{run_async_test, swc_handler::HandlerOpts};
-use swc_common::{errors::ColorConfig, SourceMap};
+use swc_common::{
+    comments::{Comments, SingleThreadedComments},
+    errors::ColorConfig,
+    SourceMap,
+};
 use swc_ecmascript::{ast::Module, codegen::text_writer::JsWriter};
 use testing::NormalizedOutput;
 
@@ -28,6 +32,8 @@ fn compile(input: PathBuf) {
         .unwrap()
         .join("output")
         .join("client.web.mjs");
+    let dts_client = input.parent().unwrap().join("output").join("client.d.ts");
+    let openapi = input.parent().unwrap().join("output").join("openapi.json");
 
     let (code, api_def) = run_async_test(
         HandlerOpts {
@@ -36,6 +42,7 @@ fn compile(input: PathBuf) {
         |env| async move {
             let project = ProjectConfig {
                 input: Arc::new(InputFiles::Files(vec![input.clone()])),
+                route_prefix: Default::default(),
             }
             .resolve(&env, Arc::new(Native {}))
             .await?;
@@ -43,7 +50,7 @@ fn compile(input: PathBuf) {
             let m = ServerApiFile::from_file(input).unwrap();
 
             let (output, api_def) = m.process(&env, project).await?;
-            let code = print(env.cm.clone(), &output);
+            let code = print(env.cm.clone(), None, &output);
 
             {
                 // Test client generation
@@ -52,6 +59,7 @@ fn compile(input: PathBuf) {
                     &api_def,
                     fnapi_client_gen::JsClientConfig {
                         target_env: fnapi_client_gen::JsTargetEnv::NodeJs,
+                        route_prefix: Default::default(),
                     },
                 )
                 .compare_to_file(&node_client)
@@ -61,10 +69,38 @@ fn compile(input: PathBuf) {
                     &api_def,
                     fnapi_client_gen::JsClientConfig {
                         target_env: fnapi_client_gen::JsTargetEnv::Web,
+                        route_prefix: Default::default(),
                     },
                 )
                 .compare_to_file(&web_client)
                 .unwrap();
+
+                let comments = SingleThreadedComments::default();
+                let dts = fnapi_client_gen::DtsClientConfig::default()
+                    .generate(
+                        &env,
+                        &ProjectApis {
+                            files: vec![api_def.clone()],
+                        },
+                        &comments,
+                    )
+                    .expect("failed to generate client declarations");
+                NormalizedOutput::from(print(Default::default(), Some(&comments), &dts))
+                    .compare_to_file(&dts_client)
+                    .unwrap();
+            }
+
+            {
+                // Test OpenAPI document generation
+                let doc = fnapi_openapi::OpenApiConfig::default()
+                    .generate(&ProjectApis {
+                        files: vec![api_def.clone()],
+                    })
+                    .expect("failed to generate OpenAPI document");
+
+                NormalizedOutput::from(serde_json::to_string_pretty(&doc).unwrap())
+                    .compare_to_file(&openapi)
+                    .unwrap();
             }
 
             Ok((code, api_def))
@@ -93,11 +129,12 @@ fn test_client_codegen(
         files: vec![api.clone()],
     };
 
+    let comments = SingleThreadedComments::default();
     let output = config
-        .generate(env, &project)
+        .generate(env, &project, &comments)
         .expect("failed to generate client");
 
-    print(Default::default(), &output).into()
+    print(Default::default(), Some(&comments), &output).into()
 }
 
 #[testing::fixture("tests/errors/**/input.ts")]
@@ -111,6 +148,7 @@ fn errors(input: PathBuf) {
         |env| async move {
             let project = ProjectConfig {
                 input: Arc::new(InputFiles::Files(vec![input.clone()])),
+                route_prefix: Default::default(),
             }
             .resolve(&env, Arc::new(Native {}))
             .await?;
@@ -128,14 +166,14 @@ fn errors(input: PathBuf) {
         .unwrap();
 }
 
-fn print(cm: Arc<SourceMap>, m: &Module) -> String {
+fn print(cm: Arc<SourceMap>, comments: Option<&dyn Comments>, m: &Module) -> String {
     let mut buf = vec![];
 
     {
         let mut emitter = swc_ecmascript::codegen::Emitter {
             cfg: Default::default(),
             cm: cm.clone(),
-            comments: None,
+            comments,
             wr: JsWriter::new(cm, "\n", &mut buf, None),
         };
 
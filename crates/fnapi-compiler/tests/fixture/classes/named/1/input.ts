@@ -0,0 +1,33 @@
+import { FnApi } from '@fnapi/api';
+
+export class UserApi {
+    @FnApi()
+    static async get(id: string): Promise<string> {
+        return id;
+    }
+}
+
+export class AdminUserApi {
+    @FnApi()
+    static async get(id: string): Promise<string> {
+        return id;
+    }
+
+    @FnApi()
+    static async ban(id: string): Promise<boolean> {
+        return true;
+    }
+}
+
+export class Helper {
+    static format(id: string): string {
+        return id;
+    }
+}
+
+export default class DefaultApi {
+    @FnApi()
+    static async ping(): Promise<string> {
+        return 'pong';
+    }
+}
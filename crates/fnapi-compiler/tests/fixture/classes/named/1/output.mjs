@@ -0,0 +1,79 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_get = wrapFnApiConfig();
+const __fnapi_config_for_get1 = wrapFnApiConfig();
+const __fnapi_config_for_ban = wrapFnApiConfig();
+const __fnapi_config_for_ping = wrapFnApiConfig();
+import '@fnapi/api';
+export const UserApi = wrapApiClass(class UserApi {
+    static async get(_req, _reply) {
+        const params = _req.params;
+        let id = params[0];
+        return id;
+    }
+}, [
+    {
+        ...__fnapi_config_for_get,
+        name: "get",
+        parameterNames: [
+            "id"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}')
+    }
+]);
+export const AdminUserApi = wrapApiClass(class AdminUserApi {
+    static async get(_req, _reply) {
+        const params = _req.params;
+        let id = params[0];
+        return id;
+    }
+    static async ban(_req, _reply) {
+        const params = _req.params;
+        let id = params[0];
+        return true;
+    }
+}, [
+    {
+        ...__fnapi_config_for_get1,
+        name: "get",
+        parameterNames: [
+            "id"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}')
+    },
+    {
+        ...__fnapi_config_for_ban,
+        name: "ban",
+        parameterNames: [
+            "id"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}')
+        ],
+        returnType: JSON.parse('{"type":"boolean"}')
+    }
+]);
+export class Helper {
+    static format(id) {
+        return id;
+    }
+}
+export default wrapApiClass(class DefaultApi {
+    static async ping(_req, _reply) {
+        return 'pong';
+    }
+}, [
+    {
+        ...__fnapi_config_for_ping,
+        name: "ping",
+        parameterNames: [],
+        parameterTypes: [],
+        returnType: JSON.parse('{"type":"string"}')
+    }
+]);
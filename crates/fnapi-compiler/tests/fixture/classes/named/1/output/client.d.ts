@@ -0,0 +1,10 @@
+export declare const UserApi: {
+    get(id: string): Promise<string>;
+};
+export declare const AdminUserApi: {
+    get(id: string): Promise<string>;
+    ban(id: string): Promise<boolean>;
+};
+export declare const DefaultApi: {
+    ping(): Promise<string>;
+};
@@ -0,0 +1,25 @@
+import __client from "@fnapi/api/client/nodejs.js";
+export const UserApi = {
+    async get (id) {
+        return __client.invoke("UserApi", "get", {
+            id: id
+        });
+    }
+};
+export const AdminUserApi = {
+    async get (id) {
+        return __client.invoke("AdminUserApi", "get", {
+            id: id
+        });
+    },
+    async ban (id) {
+        return __client.invoke("AdminUserApi", "ban", {
+            id: id
+        });
+    }
+};
+export const DefaultApi = {
+    async ping () {
+        return __client.invoke("DefaultApi", "ping", {});
+    }
+};
@@ -0,0 +1,43 @@
+import { FnApi, Query } from '@fnapi/api';
+
+interface TodoItem {
+    /**
+     * Id of the todo.
+     *
+     * @example "1"
+     */
+    id: string;
+    /** Title of the todo. */
+    title: string;
+    done: boolean;
+}
+
+export default class TodoApi {
+
+    /**
+     * Creates a todo.
+     *
+     * Todos are not done when created.
+     *
+     * @param title - Title of the todo
+     * @param {string[]} [tags] Tags of the todo
+     * @example
+     * const todo = await TodoApi.create("Buy milk");
+     * console.log(todo.id);
+     */
+    @FnApi()
+    static async create(title: string, tags?: string[]): Promise<TodoItem> {
+        return { id: '1', title, done: tags === undefined };
+    }
+
+    /**
+     * Lists todos.
+     *
+     * @param filter.done - Lists only finished todos if true
+     * @deprecated Use `search` instead
+     */
+    @FnApi({ httpMethod: 'GET' })
+    static async list(@Query() { done }: { done?: boolean }): Promise<TodoItem[]> {
+        return [{ id: '1', title: 'Buy milk', done: done === true }];
+    }
+}
@@ -0,0 +1,74 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_create = wrapFnApiConfig();
+const __fnapi_config_for_list = wrapFnApiConfig({
+    httpMethod: 'GET'
+});
+import '@fnapi/api';
+export default wrapApiClass(class TodoApi {
+    static async create(_req, _reply) {
+        const params = _req.params;
+        let title = params[0];
+        let tags = params[1];
+        return {
+            id: '1',
+            title,
+            done: tags === undefined
+        };
+    }
+    static async list(_req, _reply) {
+        const params = _req.params;
+        let { done  } = {
+            done: params[0]
+        };
+        return [
+            {
+                id: '1',
+                title: 'Buy milk',
+                done: done === true
+            }
+        ];
+    }
+}, [
+    {
+        ...__fnapi_config_for_create,
+        name: "create",
+        parameterNames: [
+            "title",
+            "tags"
+        ],
+        parameterTypes: [
+            JSON.parse('{"description":"Title of the todo","type":"string"}'),
+            JSON.parse('{"description":"Tags of the todo","oneOf":[{"items":{"type":"string"},"type":"array"}]}')
+        ],
+        returnType: JSON.parse('{"properties":{"done":{"type":"boolean"},"id":{"description":"Id of the todo.","examples":["1"],"type":"string"},"title":{"description":"Title of the todo.","type":"string"}},"required":["id","title","done"],"type":"object"}'),
+        projectResponse: (v)=>v !== null && typeof v === "object" ? {
+            id: v.id,
+            title: v.title,
+            done: v.done
+        } : v,
+        optionalParameterNames: [
+            "tags"
+        ]
+    },
+    {
+        ...__fnapi_config_for_list,
+        name: "list",
+        parameterNames: [
+            "done"
+        ],
+        parameterTypes: [
+            JSON.parse('{"description":"Lists only finished todos if true","oneOf":[{"type":"boolean"}]}')
+        ],
+        returnType: JSON.parse('{"items":{"properties":{"done":{"type":"boolean"},"id":{"description":"Id of the todo.","examples":["1"],"type":"string"},"title":{"description":"Title of the todo.","type":"string"}},"required":["id","title","done"],"type":"object"},"type":"array"}'),
+        projectResponse: (v)=>Array.isArray(v) ? v.map((v1)=>v1 !== null && typeof v1 === "object" ? {
+            id: v1.id,
+            title: v1.title,
+            done: v1.done
+        } : v1) : v,
+        optionalParameterNames: [
+            "done"
+        ],
+        queryStringJsonSchema: JSON.parse('{"properties":{"done":{"description":"Lists only finished todos if true","oneOf":[{"type":"boolean"}]}},"type":"object"}')
+    }
+]);
@@ -0,0 +1,39 @@
+export declare const TodoApi: {
+    /**
+     * Creates a todo.
+     *
+     * Todos are not done when created.
+     *
+     * @param title - Title of the todo
+     * @param tags - Tags of the todo
+     * @example
+     * const todo = await TodoApi.create("Buy milk");
+     * console.log(todo.id);
+     */ create(title: string, tags?: undefined | string[]): Promise<{
+        /**
+         * Id of the todo.
+         *
+         * @example
+         * "1"
+         */ id: string;
+        /** Title of the todo. */ title: string;
+        done: boolean;
+    }>;
+    /**
+     * Lists todos.
+     *
+     * @param p0.done - Lists only finished todos if true
+     * @deprecated Use `search` instead
+     */ list(p0: {
+        done?: undefined | boolean;
+    }): Promise<{
+        /**
+         * Id of the todo.
+         *
+         * @example
+         * "1"
+         */ id: string;
+        /** Title of the todo. */ title: string;
+        done: boolean;
+    }[]>;
+};
@@ -0,0 +1,34 @@
+import __client from "@fnapi/api/client/nodejs.js";
+export const TodoApi = {
+    /**
+     * Creates a todo.
+     *
+     * Todos are not done when created.
+     *
+     * @param title - Title of the todo
+     * @param tags - Tags of the todo
+     * @example
+     * const todo = await TodoApi.create("Buy milk");
+     * console.log(todo.id);
+     */ async create (title, tags) {
+        return __client.invoke("TodoApi", "create", {
+            title: title,
+            tags: tags
+        });
+    },
+    /**
+     * Lists todos.
+     *
+     * @param p0.done - Lists only finished todos if true
+     * @deprecated Use `search` instead
+     */ async list (p0) {
+        return __client.invoke("TodoApi", "list", {
+            done: p0?.done
+        }, {
+            method: "GET",
+            parameterLocations: {
+                done: "query"
+            }
+        });
+    }
+};
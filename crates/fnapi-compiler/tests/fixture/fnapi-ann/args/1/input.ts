@@ -2,7 +2,7 @@ import { FnApi } from '@fnapi/api';
 
 export default class TestApi {
 
-    @FnApi({ httpMethod: 'GET' })
+    @FnApi({ httpMethod: 'PUT' })
     static async test(arg1: string): Promise<string> {
 
     }
@@ -1,7 +1,7 @@
 import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
 import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
 const __fnapi_config_for_test = wrapFnApiConfig({
-    httpMethod: 'GET'
+    httpMethod: 'PUT'
 });
 import '@fnapi/api';
 export default wrapApiClass(class TestApi {
@@ -13,6 +13,9 @@ export default wrapApiClass(class TestApi {
     {
         ...__fnapi_config_for_test,
         name: "test",
+        parameterNames: [
+            "arg1"
+        ],
         parameterTypes: [
             JSON.parse('{"type":"string"}')
         ],
@@ -0,0 +1,17 @@
+/**
+ * Logs in and returns a session token.
+ *
+ * @fnapi
+ */
+export async function login(email: string): Promise<string> {
+    return email;
+}
+
+/** @fnapi */
+export async function logout(): Promise<boolean> {
+    return true;
+}
+
+export function helper(email: string): string {
+    return email;
+}
@@ -0,0 +1,40 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_login = wrapFnApiConfig();
+const __fnapi_config_for_logout = wrapFnApiConfig();
+export async function login(_req, _reply) {
+    const params = _req.params;
+    let email = params[0];
+    return email;
+}
+export async function logout(_req, _reply) {
+    return true;
+}
+export function helper(email) {
+    return email;
+}
+export const input = wrapApiClass({
+    login: login,
+    logout: logout
+}, [
+    {
+        ...__fnapi_config_for_login,
+        name: "login",
+        parameterNames: [
+            "email"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}')
+    },
+    {
+        ...__fnapi_config_for_logout,
+        name: "logout",
+        parameterNames: [],
+        parameterTypes: [],
+        returnType: JSON.parse('{"type":"boolean"}')
+    }
+], {
+    name: "input"
+});
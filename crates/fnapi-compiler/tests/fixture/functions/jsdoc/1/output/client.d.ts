@@ -0,0 +1,4 @@
+export declare const input: {
+    /** Logs in and returns a session token. */ login(email: string): Promise<string>;
+    logout(): Promise<boolean>;
+};
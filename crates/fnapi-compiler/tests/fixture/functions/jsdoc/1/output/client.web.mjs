@@ -0,0 +1,11 @@
+import __client from "@fnapi/api/client/web.js";
+export const input = {
+    /** Logs in and returns a session token. */ async login (email) {
+        return __client.invoke("input", "login", {
+            email: email
+        });
+    },
+    async logout () {
+        return __client.invoke("input", "logout", {});
+    }
+};
@@ -0,0 +1,16 @@
+import { FnApi } from '@fnapi/api';
+
+/**
+ * Logs in and returns a session token.
+ *
+ * @param email - Email of the user
+ */
+export const login = FnApi(async (email: string): Promise<string> => {
+    return email;
+});
+
+export const logout = FnApi({ httpMethod: 'GET' }, async function (): Promise<boolean> {
+    return true;
+});
+
+export const version = '1';
@@ -0,0 +1,41 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_login = wrapFnApiConfig();
+const __fnapi_config_for_logout = wrapFnApiConfig({
+    httpMethod: 'GET'
+});
+import '@fnapi/api';
+export const login = async function(_req, _reply) {
+    const params = _req.params;
+    let email = params[0];
+    return email;
+};
+export const logout = async function(_req, _reply) {
+    return true;
+};
+export const version = '1';
+export const input = wrapApiClass({
+    login: login,
+    logout: logout
+}, [
+    {
+        ...__fnapi_config_for_login,
+        name: "login",
+        parameterNames: [
+            "email"
+        ],
+        parameterTypes: [
+            JSON.parse('{"description":"Email of the user","type":"string"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}')
+    },
+    {
+        ...__fnapi_config_for_logout,
+        name: "logout",
+        parameterNames: [],
+        parameterTypes: [],
+        returnType: JSON.parse('{"type":"boolean"}')
+    }
+], {
+    name: "input"
+});
@@ -0,0 +1,8 @@
+export declare const input: {
+    /**
+     * Logs in and returns a session token.
+     *
+     * @param email - Email of the user
+     */ login(email: string): Promise<string>;
+    logout(): Promise<boolean>;
+};
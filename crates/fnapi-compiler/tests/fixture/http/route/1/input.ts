@@ -0,0 +1,21 @@
+import { FnApi, Http, Query } from '@fnapi/api';
+
+export default class TodoApi {
+    @FnApi()
+    @Http({ method: "GET", path: "/todos/:id" })
+    static async get(id: string, @Query() fields: string): Promise<string> {
+        return id;
+    }
+
+    @FnApi()
+    @Http({ method: "PUT", path: "/todos/:id/title" })
+    static async rename(id: string, title: string): Promise<boolean> {
+        return true;
+    }
+
+    @FnApi()
+    @Http({ method: "delete" })
+    static async clear(): Promise<boolean> {
+        return true;
+    }
+}
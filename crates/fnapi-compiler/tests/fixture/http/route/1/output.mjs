@@ -0,0 +1,65 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_get = wrapFnApiConfig();
+const __fnapi_config_for_rename = wrapFnApiConfig();
+const __fnapi_config_for_clear = wrapFnApiConfig();
+import '@fnapi/api';
+export default wrapApiClass(class TodoApi {
+    static async get(_req, _reply) {
+        const params = _req.params;
+        let id = params[0];
+        let fields = params[1];
+        return id;
+    }
+    static async rename(_req, _reply) {
+        const params = _req.params;
+        let id = params[0];
+        let title = params[1];
+        return true;
+    }
+    static async clear(_req, _reply) {
+        return true;
+    }
+}, [
+    {
+        ...__fnapi_config_for_get,
+        name: "get",
+        parameterNames: [
+            "id",
+            "fields"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}'),
+            JSON.parse('{"type":"string"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}'),
+        httpMethod: "GET",
+        path: "/todos/:id",
+        queryStringJsonSchema: JSON.parse('{"properties":{"fields":{"type":"string"}},"required":["fields"],"type":"object"}'),
+        paramsJsonSchema: JSON.parse('{"properties":{"id":{"type":"string"}},"required":["id"],"type":"object"}')
+    },
+    {
+        ...__fnapi_config_for_rename,
+        name: "rename",
+        parameterNames: [
+            "id",
+            "title"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}'),
+            JSON.parse('{"type":"string"}')
+        ],
+        returnType: JSON.parse('{"type":"boolean"}'),
+        httpMethod: "PUT",
+        path: "/todos/:id/title",
+        paramsJsonSchema: JSON.parse('{"properties":{"id":{"type":"string"}},"required":["id"],"type":"object"}')
+    },
+    {
+        ...__fnapi_config_for_clear,
+        name: "clear",
+        parameterNames: [],
+        parameterTypes: [],
+        returnType: JSON.parse('{"type":"boolean"}'),
+        httpMethod: "DELETE"
+    }
+]);
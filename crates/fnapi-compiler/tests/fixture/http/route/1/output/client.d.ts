@@ -0,0 +1,5 @@
+export declare const TodoApi: {
+    get(id: string, fields: string): Promise<string>;
+    rename(id: string, title: string): Promise<boolean>;
+    clear(): Promise<boolean>;
+};
@@ -0,0 +1,33 @@
+import __client from "@fnapi/api/client/web.js";
+export const TodoApi = {
+    async get (id, fields) {
+        return __client.invoke("TodoApi", "get", {
+            id: id,
+            fields: fields
+        }, {
+            method: "GET",
+            path: "/todos/:id",
+            parameterLocations: {
+                id: "path",
+                fields: "query"
+            }
+        });
+    },
+    async rename (id, title) {
+        return __client.invoke("TodoApi", "rename", {
+            id: id,
+            title: title
+        }, {
+            method: "PUT",
+            path: "/todos/:id/title",
+            parameterLocations: {
+                id: "path"
+            }
+        });
+    },
+    async clear () {
+        return __client.invoke("TodoApi", "clear", {}, {
+            method: "DELETE"
+        });
+    }
+};
@@ -12,6 +12,9 @@ export default wrapApiClass(class TestApi {
     {
         ...__fnapi_config_for_test,
         name: "test",
+        parameterNames: [
+            "arg1"
+        ],
         parameterTypes: [
             JSON.parse('{"type":"string"}')
         ],
@@ -0,0 +1,9 @@
+import { Body, FnApi, Header, Path, Query } from '@fnapi/api';
+
+export default class TodoApi {
+
+    @FnApi({ httpMethod: 'PUT' })
+    static async update(@Path() id: string, @Header() token: string, @Query() notify: boolean, @Body() title: string, done: boolean): Promise<boolean> {
+        return true;
+    }
+}
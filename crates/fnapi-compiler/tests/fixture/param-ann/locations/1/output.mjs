@@ -0,0 +1,40 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_update = wrapFnApiConfig({
+    httpMethod: 'PUT'
+});
+import '@fnapi/api';
+export default wrapApiClass(class TodoApi {
+    static async update(_req, _reply) {
+        const params = _req.params;
+        let id = params[0];
+        let token = params[1];
+        let notify = params[2];
+        let title = params[3];
+        let done = params[4];
+        return true;
+    }
+}, [
+    {
+        ...__fnapi_config_for_update,
+        name: "update",
+        parameterNames: [
+            "id",
+            "token",
+            "notify",
+            "title",
+            "done"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}'),
+            JSON.parse('{"type":"string"}'),
+            JSON.parse('{"type":"boolean"}'),
+            JSON.parse('{"type":"string"}'),
+            JSON.parse('{"type":"boolean"}')
+        ],
+        returnType: JSON.parse('{"type":"boolean"}'),
+        queryStringJsonSchema: JSON.parse('{"properties":{"notify":{"type":"boolean"}},"required":["notify"],"type":"object"}'),
+        paramsJsonSchema: JSON.parse('{"properties":{"id":{"type":"string"}},"required":["id"],"type":"object"}'),
+        headersJsonSchema: JSON.parse('{"properties":{"token":{"type":"string"}},"required":["token"],"type":"object"}')
+    }
+]);
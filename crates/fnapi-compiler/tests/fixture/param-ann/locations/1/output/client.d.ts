@@ -0,0 +1,3 @@
+export declare const TodoApi: {
+    update(id: string, token: string, notify: boolean, title: string, done: boolean): Promise<boolean>;
+};
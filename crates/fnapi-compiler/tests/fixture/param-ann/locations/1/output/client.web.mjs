@@ -0,0 +1,19 @@
+import __client from "@fnapi/api/client/web.js";
+export const TodoApi = {
+    async update (id, token, notify, title, done) {
+        return __client.invoke("TodoApi", "update", {
+            id: id,
+            token: token,
+            notify: notify,
+            title: title,
+            done: done
+        }, {
+            method: "PUT",
+            parameterLocations: {
+                id: "path",
+                token: "header",
+                notify: "query"
+            }
+        });
+    }
+};
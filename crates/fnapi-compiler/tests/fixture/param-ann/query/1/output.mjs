@@ -0,0 +1,44 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_search = wrapFnApiConfig({
+    httpMethod: 'GET'
+});
+const __fnapi_config_for_list = wrapFnApiConfig({
+    httpMethod: 'GET'
+});
+import '@fnapi/api';
+export default wrapApiClass(class TestApi {
+    static async search(_req, _reply) {
+        const params = _req.params;
+        let token = params[0];
+    }
+    static async list(_req, _reply) {
+        const params = _req.params;
+        let token = params[0];
+    }
+}, [
+    {
+        ...__fnapi_config_for_search,
+        name: "search",
+        parameterNames: [
+            "token"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}'),
+        queryStringJsonSchema: JSON.parse('{"properties":{"token":{"type":"string"}},"required":["token"],"type":"object"}')
+    },
+    {
+        ...__fnapi_config_for_list,
+        name: "list",
+        parameterNames: [
+            "token"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}'),
+        queryStringJsonSchema: JSON.parse('{"properties":{"token":{"type":"string"}},"required":["token"],"type":"object"}')
+    }
+]);
@@ -0,0 +1,4 @@
+export declare const TestApi: {
+    search(token: string): Promise<string>;
+    list(token: string): Promise<string>;
+};
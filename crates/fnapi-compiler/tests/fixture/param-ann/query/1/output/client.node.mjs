@@ -0,0 +1,23 @@
+import __client from "@fnapi/api/client/nodejs.js";
+export const TestApi = {
+    async search (token) {
+        return __client.invoke("TestApi", "search", {
+            token: token
+        }, {
+            method: "GET",
+            parameterLocations: {
+                token: "query"
+            }
+        });
+    },
+    async list (token) {
+        return __client.invoke("TestApi", "list", {
+            token: token
+        }, {
+            method: "GET",
+            parameterLocations: {
+                token: "query"
+            }
+        });
+    }
+};
@@ -0,0 +1,3 @@
+export declare const TestApi: {
+    test(p0: [string, string, number]): Promise<string>;
+};
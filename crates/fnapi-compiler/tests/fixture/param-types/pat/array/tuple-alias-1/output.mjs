@@ -11,6 +11,9 @@ export default wrapApiClass(class TestApi {
     {
         ...__fnapi_config_for_test,
         name: "test",
+        parameterNames: [
+            "p0"
+        ],
         parameterTypes: [
             JSON.parse('{"items":{"oneOf":[{"type":"string"},{"type":"string"},{"type":"number"}]},"type":"array"}')
         ],
@@ -5,14 +5,19 @@ import '@fnapi/api';
 export default wrapApiClass(class TestApi {
     static async test(_req, _reply) {
         const params = _req.params;
-        let { foo  } = params[0];
+        let { foo  } = {
+            foo: params[0]
+        };
     }
 }, [
     {
         ...__fnapi_config_for_test,
         name: "test",
+        parameterNames: [
+            "foo"
+        ],
         parameterTypes: [
-            JSON.parse('{"properties":{"foo":{"type":"string"}},"required":["foo"],"type":"object"}')
+            JSON.parse('{"type":"string"}')
         ],
         returnType: JSON.parse('{"type":"string"}')
     }
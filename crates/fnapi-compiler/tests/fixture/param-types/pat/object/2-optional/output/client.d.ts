@@ -0,0 +1,5 @@
+export declare const TestApi: {
+    test(p0: {
+        foo?: string;
+    }): Promise<string>;
+};
@@ -1,6 +1,8 @@
 import __client from "@fnapi/api/client/web.js";
 export const TestApi = {
-    async test () {
-        return __client.invoke("TestApi", "test", arguments);
+    async test (p0) {
+        return __client.invoke("TestApi", "test", {
+            foo: p0?.foo
+        });
     }
 };
@@ -12,6 +12,9 @@ export default wrapApiClass(class TestApi {
     {
         ...__fnapi_config_for_test,
         name: "test",
+        parameterNames: [
+            "a"
+        ],
         parameterTypes: [
             JSON.parse('{"oneOf":[{"properties":{"foo":{"type":"string"}},"required":["foo"],"type":"object"},{"properties":{"bar":{"type":"number"}},"required":["bar"],"type":"object"}]}')
         ],
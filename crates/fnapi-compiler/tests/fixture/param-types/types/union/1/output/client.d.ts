@@ -0,0 +1,7 @@
+export declare const TestApi: {
+    test(a: {
+        foo: string;
+    } | {
+        bar: number;
+    }): Promise<string>;
+};
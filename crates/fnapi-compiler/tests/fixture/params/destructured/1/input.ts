@@ -0,0 +1,19 @@
+import { FnApi, Query } from '@fnapi/api';
+
+interface CreateTodo {
+    title: string;
+    done?: boolean;
+}
+
+export default class TodoApi {
+
+    @FnApi()
+    static async create({ title, done = false }: CreateTodo): Promise<string> {
+        return `${title} ${done}`;
+    }
+
+    @FnApi({ httpMethod: 'GET' })
+    static async search(@Query() { keyword: k, page }: { keyword: string; page?: number }): Promise<string> {
+        return `${k} ${page}`;
+    }
+}
@@ -0,0 +1,59 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_create = wrapFnApiConfig();
+const __fnapi_config_for_search = wrapFnApiConfig({
+    httpMethod: 'GET'
+});
+import '@fnapi/api';
+export default wrapApiClass(class TodoApi {
+    static async create(_req, _reply) {
+        const params = _req.params;
+        let { title , done =false  } = {
+            title: params[0],
+            done: params[1]
+        };
+        return `${title} ${done}`;
+    }
+    static async search(_req, _reply) {
+        const params = _req.params;
+        let { keyword: k , page  } = {
+            keyword: params[0],
+            page: params[1]
+        };
+        return `${k} ${page}`;
+    }
+}, [
+    {
+        ...__fnapi_config_for_create,
+        name: "create",
+        parameterNames: [
+            "title",
+            "done"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}'),
+            JSON.parse('{"type":"boolean"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}'),
+        optionalParameterNames: [
+            "done"
+        ]
+    },
+    {
+        ...__fnapi_config_for_search,
+        name: "search",
+        parameterNames: [
+            "keyword",
+            "page"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}'),
+            JSON.parse('{"type":"number"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}'),
+        optionalParameterNames: [
+            "page"
+        ],
+        queryStringJsonSchema: JSON.parse('{"properties":{"keyword":{"type":"string"},"page":{"type":"number"}},"required":["keyword"],"type":"object"}')
+    }
+]);
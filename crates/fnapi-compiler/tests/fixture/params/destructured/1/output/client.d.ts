@@ -0,0 +1,10 @@
+export declare const TodoApi: {
+    create(p0: {
+        title: string;
+        done?: boolean;
+    }): Promise<string>;
+    search(p0: {
+        keyword: string;
+        page?: number;
+    }): Promise<string>;
+};
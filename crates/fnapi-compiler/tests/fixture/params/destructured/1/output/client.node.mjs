@@ -0,0 +1,21 @@
+import __client from "@fnapi/api/client/nodejs.js";
+export const TodoApi = {
+    async create (p0) {
+        return __client.invoke("TodoApi", "create", {
+            title: p0?.title,
+            done: p0?.done
+        });
+    },
+    async search (p0) {
+        return __client.invoke("TodoApi", "search", {
+            keyword: p0?.keyword,
+            page: p0?.page
+        }, {
+            method: "GET",
+            parameterLocations: {
+                keyword: "query",
+                page: "query"
+            }
+        });
+    }
+};
@@ -0,0 +1,9 @@
+import { FnApi } from '@fnapi/api';
+
+export default class TodoApi {
+
+    @FnApi()
+    static async rename(p1: string, { title }: { title: string }): Promise<string> {
+        return `${p1} ${title}`;
+    }
+}
@@ -0,0 +1,28 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_rename = wrapFnApiConfig();
+import '@fnapi/api';
+export default wrapApiClass(class TodoApi {
+    static async rename(_req, _reply) {
+        const params = _req.params;
+        let p1 = params[0];
+        let { title  } = {
+            title: params[1]
+        };
+        return `${p1} ${title}`;
+    }
+}, [
+    {
+        ...__fnapi_config_for_rename,
+        name: "rename",
+        parameterNames: [
+            "p1",
+            "title"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}'),
+            JSON.parse('{"type":"string"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}')
+    }
+]);
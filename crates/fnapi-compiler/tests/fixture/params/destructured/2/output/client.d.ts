@@ -0,0 +1,5 @@
+export declare const TodoApi: {
+    rename(p1: string, _p1: {
+        title: string;
+    }): Promise<string>;
+};
@@ -0,0 +1,9 @@
+import __client from "@fnapi/api/client/nodejs.js";
+export const TodoApi = {
+    async rename (p1, _p1) {
+        return __client.invoke("TodoApi", "rename", {
+            p1: p1,
+            title: _p1?.title
+        });
+    }
+};
@@ -0,0 +1,14 @@
+import { FnApi, Query } from '@fnapi/api';
+
+export default class TodoApi {
+
+    @FnApi()
+    static async search(keyword: string, limit: number = 10, @Query() page?: number): Promise<string> {
+        return `${keyword} ${limit} ${page}`;
+    }
+
+    @FnApi()
+    static async count(tag: string = "all", done: boolean): Promise<number> {
+        return 0;
+    }
+}
@@ -0,0 +1,57 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_search = wrapFnApiConfig();
+const __fnapi_config_for_count = wrapFnApiConfig();
+import '@fnapi/api';
+export default wrapApiClass(class TodoApi {
+    static async search(_req, _reply) {
+        const params = _req.params;
+        let keyword = params[0];
+        let limit = params[1] === undefined ? 10 : params[1];
+        let page = params[2];
+        return `${keyword} ${limit} ${page}`;
+    }
+    static async count(_req, _reply) {
+        const params = _req.params;
+        let tag = params[0] === undefined ? "all" : params[0];
+        let done = params[1];
+        return 0;
+    }
+}, [
+    {
+        ...__fnapi_config_for_search,
+        name: "search",
+        parameterNames: [
+            "keyword",
+            "limit",
+            "page"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}'),
+            JSON.parse('{"type":"number"}'),
+            JSON.parse('{"type":"number"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}'),
+        optionalParameterNames: [
+            "limit",
+            "page"
+        ],
+        queryStringJsonSchema: JSON.parse('{"properties":{"page":{"type":"number"}},"type":"object"}')
+    },
+    {
+        ...__fnapi_config_for_count,
+        name: "count",
+        parameterNames: [
+            "tag",
+            "done"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}'),
+            JSON.parse('{"type":"boolean"}')
+        ],
+        returnType: JSON.parse('{"type":"number"}'),
+        optionalParameterNames: [
+            "tag"
+        ]
+    }
+]);
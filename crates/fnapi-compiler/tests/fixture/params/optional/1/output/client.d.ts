@@ -0,0 +1,4 @@
+export declare const TodoApi: {
+    search(keyword: string, limit?: number, page?: number): Promise<string>;
+    count(tag: string | undefined, done: boolean): Promise<number>;
+};
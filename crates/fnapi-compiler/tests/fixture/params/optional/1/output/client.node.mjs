@@ -0,0 +1,20 @@
+import __client from "@fnapi/api/client/nodejs.js";
+export const TodoApi = {
+    async search (keyword, limit, page) {
+        return __client.invoke("TodoApi", "search", {
+            keyword: keyword,
+            limit: limit,
+            page: page
+        }, {
+            parameterLocations: {
+                page: "query"
+            }
+        });
+    },
+    async count (tag, done) {
+        return __client.invoke("TodoApi", "count", {
+            tag: tag,
+            done: done
+        });
+    }
+};
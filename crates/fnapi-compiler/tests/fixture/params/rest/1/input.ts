@@ -0,0 +1,9 @@
+import { FnApi } from '@fnapi/api';
+
+export default class TodoApi {
+
+    @FnApi()
+    static async tag(id: string, ...tags: string[]): Promise<number> {
+        return tags.length;
+    }
+}
@@ -0,0 +1,29 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_tag = wrapFnApiConfig();
+import '@fnapi/api';
+export default wrapApiClass(class TodoApi {
+    static async tag(_req, _reply) {
+        const params = _req.params;
+        let id = params[0];
+        let tags = params[1] === undefined ? [] : params[1];
+        return tags.length;
+    }
+}, [
+    {
+        ...__fnapi_config_for_tag,
+        name: "tag",
+        parameterNames: [
+            "id",
+            "tags"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}'),
+            JSON.parse('{"items":{"type":"string"},"type":"array"}')
+        ],
+        returnType: JSON.parse('{"type":"number"}'),
+        optionalParameterNames: [
+            "tags"
+        ]
+    }
+]);
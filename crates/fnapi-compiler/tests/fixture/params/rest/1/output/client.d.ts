@@ -0,0 +1,3 @@
+export declare const TodoApi: {
+    tag(id: string, ...tags: string[]): Promise<number>;
+};
@@ -0,0 +1,9 @@
+import __client from "@fnapi/api/client/web.js";
+export const TodoApi = {
+    async tag (id, ...tags) {
+        return __client.invoke("TodoApi", "tag", {
+            id: id,
+            tags: tags
+        });
+    }
+};
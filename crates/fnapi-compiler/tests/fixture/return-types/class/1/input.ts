@@ -0,0 +1,21 @@
+import { FnApi } from '@fnapi/api';
+
+class User {
+    id: string;
+
+    constructor(id: string) {
+        this.id = id;
+    }
+
+    greet(): string {
+        return `Hello, ${this.id}`;
+    }
+}
+
+export default class TestApi {
+    @FnApi()
+    static async test(arg1: string): Promise<User> {
+        // `passwordHash` is not declared by `User`, so it's not sent.
+        return Object.assign(new User(arg1), { passwordHash: "secret" });
+    }
+}
@@ -0,0 +1,37 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_test = wrapFnApiConfig();
+import '@fnapi/api';
+class User {
+    constructor(id){
+        this.id = id;
+    }
+    greet() {
+        return `Hello, ${this.id}`;
+    }
+}
+export default wrapApiClass(class TestApi {
+    static async test(_req, _reply) {
+        const params = _req.params;
+        let arg1 = params[0];
+        // `passwordHash` is not declared by `User`, so it's not sent.
+        return Object.assign(new User(arg1), {
+            passwordHash: "secret"
+        });
+    }
+}, [
+    {
+        ...__fnapi_config_for_test,
+        name: "test",
+        parameterNames: [
+            "arg1"
+        ],
+        parameterTypes: [
+            JSON.parse('{"type":"string"}')
+        ],
+        returnType: JSON.parse('{"properties":{"id":{"type":"string"}},"required":["id"],"type":"object"}'),
+        projectResponse: (v)=>v !== null && typeof v === "object" ? {
+            id: v.id
+        } : v
+    }
+]);
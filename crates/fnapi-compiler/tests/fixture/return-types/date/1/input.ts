@@ -0,0 +1,9 @@
+import { FnApi } from '@fnapi/api';
+
+
+export default class TestApi {
+    @FnApi()
+    static async test(arg1: string): Promise<Date> {
+
+    }
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+export declare const TestApi: {
+    test(arg1: string): Promise<{
+        id: string;
+        [key: string]: string;
+    }>;
+};
@@ -15,9 +15,16 @@ export default wrapApiClass(class TestApi {
     {
         ...__fnapi_config_for_test,
         name: "test",
+        parameterNames: [
+            "a"
+        ],
         parameterTypes: [
             JSON.parse('{"type":"string"}')
         ],
-        returnType: JSON.parse('{"allOf":[{"properties":{"foo":{"type":"string"}},"required":["foo"],"type":"object"},{"properties":{"bar":{"type":"number"}},"required":["bar"],"type":"object"}]}')
+        returnType: JSON.parse('{"allOf":[{"properties":{"foo":{"type":"string"}},"required":["foo"],"type":"object"},{"properties":{"bar":{"type":"number"}},"required":["bar"],"type":"object"}]}'),
+        projectResponse: (v)=>v !== null && typeof v === "object" ? {
+            foo: v.foo,
+            bar: v.bar
+        } : v
     }
 ]);
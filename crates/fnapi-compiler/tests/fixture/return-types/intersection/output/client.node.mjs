@@ -1,6 +1,8 @@
 import __client from "@fnapi/api/client/nodejs.js";
 export const TestApi = {
-    async test () {
-        return __client.invoke("TestApi", "test", arguments);
+    async test (a) {
+        return __client.invoke("TestApi", "test", {
+            a: a
+        });
     }
 };
@@ -11,9 +11,15 @@ export default wrapApiClass(class TestApi {
     {
         ...__fnapi_config_for_test,
         name: "test",
+        parameterNames: [
+            "arg1"
+        ],
         parameterTypes: [
             JSON.parse('{"type":"string"}')
         ],
-        returnType: JSON.parse('{"properties":{"foo":{"type":"string"}},"required":["foo"],"type":"object"}')
+        returnType: JSON.parse('{"properties":{"foo":{"type":"string"}},"required":["foo"],"type":"object"}'),
+        projectResponse: (v)=>v !== null && typeof v === "object" ? {
+            foo: v.foo
+        } : v
     }
 ]);
@@ -11,9 +11,19 @@ export default wrapApiClass(class TestApi {
     {
         ...__fnapi_config_for_test,
         name: "test",
+        parameterNames: [
+            "arg1"
+        ],
         parameterTypes: [
             JSON.parse('{"type":"string"}')
         ],
-        returnType: JSON.parse('{"properties":{"foo":{"properties":{"foo":{"properties":{"bar":{"type":"string"}},"required":[],"type":"object"}},"required":[],"type":"object"}},"required":[],"type":"object"}')
+        returnType: JSON.parse('{"properties":{"foo":{"properties":{"foo":{"properties":{"bar":{"type":"string"}},"required":[],"type":"object"}},"required":[],"type":"object"}},"required":[],"type":"object"}'),
+        projectResponse: (v)=>v !== null && typeof v === "object" ? {
+            foo: v.foo !== null && typeof v.foo === "object" ? {
+                foo: v.foo.foo !== null && typeof v.foo.foo === "object" ? {
+                    bar: v.foo.foo.bar
+                } : v.foo.foo
+            } : v.foo
+        } : v
     }
 ]);
@@ -0,0 +1,9 @@
+export declare const TestApi: {
+    test(arg1: string): Promise<{
+        foo?: {
+            foo?: {
+                bar?: string;
+            };
+        };
+    }>;
+};
@@ -0,0 +1,13 @@
+import { FnApi } from '@fnapi/api';
+
+interface Todo {
+    id: string;
+}
+
+
+export default class TestApi {
+    @FnApi()
+    static async test(arg1: string): Promise<Record<string, Todo>> {
+
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+export declare const TestApi: {
+    test(arg1: string): Promise<{
+        [key: string]: {
+            id: string;
+        };
+    }>;
+};
@@ -0,0 +1,5 @@
+export declare const TestApi: {
+    test(arg1: string): Promise<{
+        [key: number]: string;
+    }>;
+};
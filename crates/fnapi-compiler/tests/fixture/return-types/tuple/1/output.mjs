@@ -11,9 +11,15 @@ export default wrapApiClass(class TestApi {
     {
         ...__fnapi_config_for_test,
         name: "test",
+        parameterNames: [
+            "arg1"
+        ],
         parameterTypes: [
             JSON.parse('{"type":"string"}')
         ],
-        returnType: JSON.parse('{"items":{"oneOf":[{"type":"string"}]},"type":"array"}')
+        returnType: JSON.parse('{"items":{"oneOf":[{"type":"string"}]},"type":"array"}'),
+        projectResponse: (v)=>Array.isArray(v) ? [
+            v[0]
+        ] : v
     }
 ]);
@@ -0,0 +1,3 @@
+export declare const TestApi: {
+    test(arg1: string): Promise<[string]>;
+};
@@ -1,6 +1,8 @@
 import __client from "@fnapi/api/client/web.js";
 export const TestApi = {
-    async test () {
-        return __client.invoke("TestApi", "test", arguments);
+    async test (arg1) {
+        return __client.invoke("TestApi", "test", {
+            arg1: arg1
+        });
     }
 };
@@ -11,9 +11,22 @@ export default wrapApiClass(class TestApi {
     {
         ...__fnapi_config_for_test,
         name: "test",
+        parameterNames: [
+            "arg1"
+        ],
         parameterTypes: [
             JSON.parse('{"type":"string"}')
         ],
-        returnType: JSON.parse('{"items":{"oneOf":[{"properties":{"foo":{"type":"string"}},"required":["foo"],"type":"object"},{"type":"number"},{"properties":{"bar":{"type":"number"},"foo":{"type":"number"}},"required":["foo"],"type":"object"}]},"type":"array"}')
+        returnType: JSON.parse('{"items":{"oneOf":[{"properties":{"foo":{"type":"string"}},"required":["foo"],"type":"object"},{"type":"number"},{"properties":{"bar":{"type":"number"},"foo":{"type":"number"}},"required":["foo"],"type":"object"}]},"type":"array"}'),
+        projectResponse: (v)=>Array.isArray(v) ? [
+            v[0] !== null && typeof v[0] === "object" ? {
+                foo: v[0].foo
+            } : v[0],
+            v[1],
+            v[2] !== null && typeof v[2] === "object" ? {
+                foo: v[2].foo,
+                bar: v[2].bar
+            } : v[2]
+        ] : v
     }
 ]);
@@ -0,0 +1,8 @@
+export declare const TestApi: {
+    test(arg1: string): Promise<[{
+            foo: string;
+        }, number, {
+            foo: number;
+            bar?: number;
+        }]>;
+};
@@ -0,0 +1,7 @@
+export declare const TestApi: {
+    test(a: string): Promise<{
+        foo: string;
+    } | {
+        bar: number;
+    }>;
+};
@@ -0,0 +1,10 @@
+import { FnApi, Validate, validators } from '@fnapi/api';
+
+export default class AuthApi {
+    @FnApi()
+    @Validate("email", [validators.email])
+    @Validate("password", [validators.required, validators.minLength(8), validators.maxLength(64)])
+    static async login(email: string, password: string): Promise<string> {
+        return email;
+    }
+}
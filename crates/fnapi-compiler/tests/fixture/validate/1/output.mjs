@@ -0,0 +1,26 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_login = wrapFnApiConfig();
+import '@fnapi/api';
+export default wrapApiClass(class AuthApi {
+    static async login(_req, _reply) {
+        const params = _req.params;
+        let email = params[0];
+        let password = params[1];
+        return email;
+    }
+}, [
+    {
+        ...__fnapi_config_for_login,
+        name: "login",
+        parameterNames: [
+            "email",
+            "password"
+        ],
+        parameterTypes: [
+            JSON.parse('{"format":"email","type":"string"}'),
+            JSON.parse('{"maxLength":64,"minLength":8,"type":"string"}')
+        ],
+        returnType: JSON.parse('{"type":"string"}')
+    }
+]);
@@ -0,0 +1,3 @@
+export declare const AuthApi: {
+    login(email: string, password: string): Promise<string>;
+};
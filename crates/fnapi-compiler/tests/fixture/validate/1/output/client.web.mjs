@@ -0,0 +1,18 @@
+import __client from "@fnapi/api/client/web.js";
+import * as __validators from "@fnapi/api/validators/index.js";
+export const AuthApi = {
+    async login (email, password) {
+        __validators.check("email", email, [
+            __validators.email
+        ]);
+        __validators.check("password", password, [
+            __validators.required,
+            __validators.minLength(8),
+            __validators.maxLength(64)
+        ]);
+        return __client.invoke("AuthApi", "login", {
+            email: email,
+            password: password
+        });
+    }
+};
@@ -0,0 +1,9 @@
+import { FnApi, Validate, validators } from '@fnapi/api';
+
+export default class SearchApi {
+    @FnApi()
+    @Validate("query", [validators.required])
+    static async search(query?: string, limit?: number): Promise<string> {
+        return `${query}:${limit}`;
+    }
+}
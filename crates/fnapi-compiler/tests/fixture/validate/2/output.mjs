@@ -0,0 +1,29 @@
+import wrapApiClass from "@fnapi/api/rt/wrapApiClass.js";
+import wrapFnApiConfig from "@fnapi/api/rt/wrapFnApiConfig.js";
+const __fnapi_config_for_search = wrapFnApiConfig();
+import '@fnapi/api';
+export default wrapApiClass(class SearchApi {
+    static async search(_req, _reply) {
+        const params = _req.params;
+        let query = params[0];
+        let limit = params[1];
+        return `${query}:${limit}`;
+    }
+}, [
+    {
+        ...__fnapi_config_for_search,
+        name: "search",
+        parameterNames: [
+            "query",
+            "limit"
+        ],
+        parameterTypes: [
+            JSON.parse('{"oneOf":[{"type":"string"}]}'),
+            JSON.parse('{"oneOf":[{"type":"number"}]}')
+        ],
+        returnType: JSON.parse('{"type":"string"}'),
+        optionalParameterNames: [
+            "limit"
+        ]
+    }
+]);
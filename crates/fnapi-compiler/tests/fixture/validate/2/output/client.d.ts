@@ -0,0 +1,3 @@
+export declare const SearchApi: {
+    search(query?: undefined | string, limit?: undefined | number): Promise<string>;
+};
@@ -0,0 +1,13 @@
+import __client from "@fnapi/api/client/web.js";
+import * as __validators from "@fnapi/api/validators/index.js";
+export const SearchApi = {
+    async search (query, limit) {
+        __validators.check("query", query, [
+            __validators.required
+        ]);
+        return __client.invoke("SearchApi", "search", {
+            query: query,
+            limit: limit
+        });
+    }
+};
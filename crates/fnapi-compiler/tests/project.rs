@@ -32,6 +32,7 @@ fn exec(tsconfig_json: PathBuf) {
         |env| async move {
             let project = ProjectConfig {
                 input: Arc::new(InputFiles::TsConfig(tsconfig_json)),
+                route_prefix: Default::default(),
             }
             .resolve(&env, Arc::new(Native {}))
             .await?;
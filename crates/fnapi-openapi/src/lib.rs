@@ -0,0 +1,146 @@
+//! OpenAPI document generator for fnapi.
+
+use anyhow::Result;
+use fnapi_api_def::{
+    types::json_schema::ToJsonSchema, ApiClass, ApiFn, ParamLocation, ProjectApis,
+};
+use serde_json::{json, Map, Value};
+
+/// Version of the OpenAPI specification used for the generated documents.
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OpenApiConfig {
+    /// `info.title` of the document.
+    pub title: String,
+
+    /// `info.version` of the document.
+    pub version: String,
+
+    /// Prefix for the path of all routes, like `/api`.
+    pub route_prefix: Option<String>,
+}
+
+impl Default for OpenApiConfig {
+    fn default() -> Self {
+        Self {
+            title: "FnApi".into(),
+            version: "0.0.0".into(),
+            route_prefix: Default::default(),
+        }
+    }
+}
+
+impl OpenApiConfig {
+    pub fn generate(&self, project: &ProjectApis) -> Result<Value> {
+        let mut paths = Map::default();
+
+        for class in project.classes() {
+            for f in class.functions.iter() {
+                let route = f.route(self.route_prefix.as_deref(), &class.class_name);
+
+                // Routes may share a path with different methods.
+                let path = paths
+                    .entry(openapi_path(&route))
+                    .or_insert_with(|| Value::Object(Map::default()));
+                path[f.method.as_str().to_ascii_lowercase()] = self.generate_operation(class, f);
+            }
+        }
+
+        Ok(json!({
+            "openapi": OPENAPI_VERSION,
+            "info": {
+                "title": self.title,
+                "version": self.version,
+            },
+            "paths": paths,
+        }))
+    }
+
+    fn generate_operation(&self, class: &ApiClass, f: &ApiFn) -> Value {
+        let mut operation = Map::default();
+
+        operation.insert(
+            "operationId".into(),
+            Value::String(format!("{}.{}", class.class_name, f.name)),
+        );
+        operation.insert(
+            "tags".into(),
+            Value::Array(vec![Value::String(class.class_name.to_string())]),
+        );
+
+        // The first line of the JSDoc comment is the summary.
+        if let Some(description) = &f.docs.description {
+            let summary = description.lines().next().unwrap_or_default();
+            operation.insert("summary".into(), Value::String(summary.into()));
+            operation.insert("description".into(), Value::String(description.clone()));
+        }
+        if f.docs.deprecated.is_some() {
+            operation.insert("deprecated".into(), Value::Bool(true));
+        }
+
+        let parameters = f
+            .request_params()
+            .into_iter()
+            .filter(|(_, param)| param.location != ParamLocation::Body)
+            .map(|(key, param)| {
+                let mut parameter = json!({
+                    "name": key,
+                    "in": param.location.as_str(),
+                    "required": param.is_required(),
+                    "schema": param.to_json_schema(),
+                });
+                if let Some(description) = &param.description {
+                    parameter["description"] = Value::String(description.clone());
+                }
+                parameter
+            })
+            .collect::<Vec<_>>();
+        if !parameters.is_empty() {
+            operation.insert("parameters".into(), Value::Array(parameters));
+        }
+
+        // This should match `parseParams` of `wrapApiClass`.
+        if let Some(body) = f.params_schema(ParamLocation::Body) {
+            operation.insert(
+                "requestBody".into(),
+                json!({
+                    "required": body.contains_key("required"),
+                    "content": {
+                        "application/json": {
+                            "schema": body,
+                        }
+                    }
+                }),
+            );
+        }
+
+        operation.insert(
+            "responses".into(),
+            json!({
+                "200": {
+                    "description": "Successful response",
+                    "content": {
+                        "application/json": {
+                            "schema": f.return_type.to_json_schema(),
+                        }
+                    }
+                }
+            }),
+        );
+
+        Value::Object(operation)
+    }
+}
+
+/// Converts `/todos/:id` to `/todos/{id}`.
+fn openapi_path(route: &str) -> String {
+    route
+        .split('/')
+        .map(|segment| match segment.strip_prefix(':') {
+            Some(name) => format!("{{{}}}", name),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -4,7 +4,7 @@ use std::{env, future::Future};
 
 use anyhow::Result;
 use fnapi_core::Env;
-use swc_common::{sync::Lrc, SourceMap};
+use swc_common::{sync::Lrc, Globals, SourceMap};
 use swc_handler::{try_with_handler, HandlerOpts};
 use tracing_subscriber::EnvFilter;
 
@@ -40,7 +40,7 @@ where
     try_with_handler(cm.clone(), opts, |handler| {
         let env = Env {
             cm,
-            globals: Default::default(),
+            globals: Lrc::new(Globals::new()),
             handler,
         };
 
@@ -0,0 +1,133 @@
+//! Incremental build cache, stored in `.fnapi/cache`.
+
+use std::{
+    fs::{create_dir_all, read, read_to_string, write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{Context, Result};
+use fnapi_api_def::ApiFile;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+
+use crate::commands::CompiledFile;
+
+/// Cached output of an api file.
+///
+/// An entry is valid only if the build of the compiler, build options, the
+/// file and all of its dependencies are not modified.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    /// [fnapi_compiler::BUILD_ID]
+    compiler_build: String,
+    options: String,
+
+    path: PathBuf,
+    hash: String,
+    dependencies: Vec<DependencyHash>,
+
+    code: String,
+    source_map: String,
+    api: Arc<ApiFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DependencyHash {
+    path: PathBuf,
+    hash: String,
+}
+
+pub(crate) struct BuildCache {
+    dir: PathBuf,
+
+    /// Build options which affect the output, like the server target.
+    options: String,
+}
+
+impl BuildCache {
+    pub fn new(dir: PathBuf, options: String) -> Self {
+        Self { dir, options }
+    }
+
+    /// Returns the cached output of `path`, if it's still valid.
+    pub fn get(&self, path: &Path) -> Option<CompiledFile> {
+        let s = read_to_string(self.entry_path(path)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&s).ok()?;
+
+        if entry.compiler_build != fnapi_compiler::BUILD_ID
+            || entry.options != self.options
+            || entry.path != path
+            || entry.hash != hash_file(path).ok()?
+        {
+            return None;
+        }
+
+        for dep in &entry.dependencies {
+            if dep.hash != hash_file(&dep.path).ok()? {
+                return None;
+            }
+        }
+
+        Some(CompiledFile {
+            path: entry.path,
+            code: entry.code,
+            source_map: entry.source_map,
+            api: entry.api,
+        })
+    }
+
+    /// Stores the output of `file`, which depends on `dependencies`.
+    pub fn put(&self, file: &CompiledFile, dependencies: &[PathBuf]) -> Result<()> {
+        let entry = CacheEntry {
+            compiler_build: fnapi_compiler::BUILD_ID.to_string(),
+            options: self.options.clone(),
+            path: file.path.clone(),
+            hash: hash_file(&file.path)?,
+            dependencies: dependencies
+                .iter()
+                .map(|path| {
+                    Ok(DependencyHash {
+                        path: path.clone(),
+                        hash: hash_file(path)?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+            code: file.code.clone(),
+            source_map: file.source_map.clone(),
+            api: file.api.clone(),
+        };
+
+        create_dir_all(&self.dir).context("failed to create cache directory")?;
+
+        write(self.entry_path(&file.path), serde_json::to_string(&entry)?)
+            .with_context(|| format!("failed to write cache for `{}`", file.path.display()))
+    }
+
+    fn entry_path(&self, path: &Path) -> PathBuf {
+        let key = Sha1::digest(path.display().to_string().as_bytes());
+
+        self.dir.join(format!("{:x}.json", key))
+    }
+}
+
+/// Returns a hash of the paths and the contents of `paths`, which can be a
+/// part of the build options.
+pub(crate) fn hash_files(paths: &[PathBuf]) -> Result<String> {
+    let mut hasher = Sha1::new();
+
+    for path in paths {
+        hasher.update(path.display().to_string().as_bytes());
+        hasher.update(hash_file(path)?.as_bytes());
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let content = read(path).with_context(|| format!("failed to read `{}`", path.display()))?;
+
+    Ok(format!("{:x}", Sha1::digest(&content)))
+}
@@ -1,63 +1,134 @@
 use std::{
+    collections::HashMap,
     fs::{create_dir_all, write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
-use anyhow::{bail, Context, Error, Result};
+use anyhow::{bail, Context, Result};
 use clap::{ArgEnum, Parser};
-use fnapi_api_def::ProjectApis;
-use fnapi_client_gen::JsTargetEnv;
+use fnapi_api_def::{diff::diff_projects, ApiFile, ApiManifest, ProjectApis};
+use fnapi_client_gen::{DtsClientConfig, JsTargetEnv};
 use fnapi_compiler::{
-    project::{InputFiles, ProjectConfig},
+    project::{InputFiles, Project, ProjectConfig},
     target::{AwsLambda, Native, NextJs, ServerTarget, ServerlessService},
     ServerApiFile,
 };
 use fnapi_core::Env;
+use fnapi_openapi::OpenApiConfig;
 use futures::future::join_all;
+use ignore::WalkBuilder;
 use rayon::prelude::*;
-use swc_common::SourceMap;
+use serde::Deserialize;
+use swc_common::{
+    comments::{Comments, SingleThreadedComments},
+    errors::{DiagnosticBuilder, Emitter, Handler},
+    source_map::SourceMapGenConfig,
+    FileName, SourceMap,
+};
 use swc_ecmascript::{ast::Module, codegen::text_writer::JsWriter};
 use tokio::{spawn, task::yield_now};
 
+use super::diff::{load_api_def, print_changes};
+use crate::{
+    cache::{hash_files, BuildCache},
+    config::{FnApiConfig, InputFilter},
+};
+
+/// Extensions of files which may be api files.
+const SOURCE_FILE_EXTENSIONS: &[&str] = &[".ts", ".tsx", ".mts", ".cts"];
+
 /// Build functions as a server and generate client sdk.
 #[derive(Parser, Debug)]
 pub(crate) struct BuildCommand {
     /// Directories or files to build
     inputs: Vec<PathBuf>,
 
-    /// Option to deploy fnapi server to external providers.
-    #[clap(arg_enum, long, short = 't', default_value = "fnapi")]
-    server_target: Target,
+    /// Build files of a `tsconfig.json` instead of `inputs`, so the type
+    /// checker uses its compiler options.
+    #[clap(long, short = 'p', conflicts_with = "inputs")]
+    project: Option<PathBuf>,
 
-    /// Client types to generate.
+    /// Glob patterns to select api files. With `--project`, they are relative
+    /// to the directory of the `tsconfig.json`. Defaults to all files.
+    #[clap(long)]
+    include: Vec<String>,
+
+    /// Option to deploy fnapi server to external providers. Defaults to
+    /// `fnapi`
+    #[clap(arg_enum, long, short = 't')]
+    server_target: Option<Target>,
+
+    /// Client types to generate. Defaults to `node` and `web`
     #[clap(arg_enum, long, short = 'c')]
     client_types: Vec<ClientType>,
 
-    /// Directory to use for generated client api.
+    /// Directory to use for generated client api. Defaults to the fnapi
+    /// directory
     #[clap(long, short = 'd')]
     client_target_dir: Option<PathBuf>,
 
-    /// FnApi directory for printing the api definition. Defaults to `.fnapi`
+    /// FnApi directory for printing the api definition (`api.json`). Defaults
+    /// to `.fnapi`
     #[clap(long)]
     fnapi_dir: Option<PathBuf>,
+
+    /// Write an OpenAPI document as `openapi.json` in the fnapi directory.
+    #[clap(long)]
+    openapi: bool,
+
+    /// Snapshot of the api surface. Updated on each build unless `--locked`
+    /// is used.
+    #[clap(long)]
+    snapshot: Option<PathBuf>,
+
+    /// Fail if the api surface differs from the snapshot.
+    #[clap(long, requires = "snapshot")]
+    locked: bool,
+
+    /// Compile all files without using the build cache in the fnapi
+    /// directory.
+    #[clap(long)]
+    no_cache: bool,
 }
 
-#[derive(ArgEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(ArgEnum, Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 pub(crate) enum ClientType {
-    /// Typescript
+    /// Javascript client for node.js, written as `client.node.mjs`
+    #[serde(rename = "node")]
+    Node,
+
+    /// Javascript client for browsers, written as `client.web.mjs`
+    #[serde(rename = "web")]
     Web,
+
+    /// Typescript declarations for the clients, written as `client.d.ts`
+    #[serde(rename = "dts")]
+    Dts,
+}
+
+impl ClientType {
+    fn file_name(self) -> &'static str {
+        match self {
+            ClientType::Node => "client.node.mjs",
+            ClientType::Web => "client.web.mjs",
+            ClientType::Dts => "client.d.ts",
+        }
+    }
 }
 
-#[derive(ArgEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(ArgEnum, Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 pub(crate) enum Target {
     #[clap(name = "fnapi")]
+    #[serde(rename = "fnapi")]
     Native,
 
     #[clap(name = "nextjs")]
+    #[serde(rename = "nextjs")]
     NextJs,
 
     #[clap(name = "lambda")]
+    #[serde(rename = "lambda")]
     AwsLambda,
 }
 
@@ -67,21 +138,21 @@ impl Default for Target {
     }
 }
 
+impl Target {
+    pub(super) fn to_server_target(self) -> Arc<dyn ServerTarget> {
+        match self {
+            Target::Native => Arc::new(Native {}),
+            Target::NextJs => Arc::new(ServerlessService::new(NextJs {})),
+            Target::AwsLambda => Arc::new(ServerlessService::new(AwsLambda {})),
+        }
+    }
+}
+
 impl BuildCommand {
-    pub async fn run(self, env: &Env) -> Result<()> {
-        let inputs = self
-            .inputs
-            .into_par_iter()
-            .map(expand_dir)
-            .collect::<Result<Vec<_>>>()
-            .context("failed to expand inputs")?
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>();
+    pub async fn run(mut self, env: &Env, config: &FnApiConfig) -> Result<()> {
+        self.merge_config(config);
 
-        if inputs.is_empty() {
-            bail!("no inputs found");
-        }
+        let filter = InputFilter::new(&config.root, &self.include, &config.exclude)?;
 
         let fnapi_dir = self.fnapi_dir.unwrap_or_else(|| PathBuf::from(".fnapi"));
         let fnapi_server_dir = fnapi_dir.join("server");
@@ -89,113 +160,710 @@ impl BuildCommand {
         create_dir_all(&fnapi_dir).context("failed to create fnapi directory")?;
         create_dir_all(&fnapi_server_dir).context("failed to create fnapi server directory")?;
 
-        let server_target: Arc<dyn ServerTarget> = match self.server_target {
-            Target::Native => Arc::new(Native {}),
-            Target::NextJs => Arc::new(ServerlessService::new(NextJs {})),
-            Target::AwsLambda => Arc::new(ServerlessService::new(AwsLambda {})),
+        let server_target = self.server_target.unwrap_or_default();
+
+        let ApiInputs {
+            // The project is resolved only if required, as it starts the type
+            // server.
+            project,
+            api_files,
+            ..
+        } = ApiInputs::select(
+            env,
+            config,
+            self.project,
+            self.inputs,
+            &filter,
+            server_target,
+        )
+        .await?
+        .non_empty()?;
+
+        let cache = if self.no_cache {
+            None
+        } else {
+            let mut options = format!("{:?} {:?}", server_target, config.route_prefix());
+
+            // Types of all files depend on the compiler options and global
+            // declarations of the project.
+            if let Some(project) = &project {
+                options.push(' ');
+                options.push_str(&hash_files(&project.config_files().await?)?);
+            }
+
+            Some(BuildCache::new(fnapi_dir.join("cache"), options))
         };
 
-        let project = ProjectConfig {
-            input: Arc::new(InputFiles::Files(inputs)),
-        }
-        .resolve(env, server_target)
-        .await
-        .context("failed to resolve project")?;
+        let mut files = api_files
+            .iter()
+            .map(|path| cache.as_ref().and_then(|cache| cache.get(path)))
+            .collect::<Vec<_>>();
+        let changed = api_files
+            .iter()
+            .zip(&files)
+            .filter(|(_, file)| file.is_none())
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
 
-        let mut handles = vec![];
+        if !changed.is_empty() {
+            let project = match project {
+                Some(project) => project,
+                None => {
+                    resolve_project(
+                        env,
+                        config,
+                        InputFiles::Files(api_files.clone()),
+                        server_target,
+                    )
+                    .await?
+                }
+            };
 
-        for file in project.files.iter().cloned() {
-            let project = project.clone();
-            let env = env.clone();
+            let compiled = compile_files(env, &project, &changed).await.finish()?;
 
-            handles.push(spawn(async move {
-                let m = ServerApiFile::from_file(file.clone())?;
-                let (module, api_file) = m.process(&env, project).await?;
+            if let Some(cache) = &cache {
+                for file in &compiled {
+                    cache.put(file, &project.dependencies_of(&file.path).await?)?;
+                }
+            }
 
-                Ok::<_, Error>((file, module, api_file))
-            }));
+            let mut compiled = compiled.into_iter();
+            for file in files.iter_mut().filter(|file| file.is_none()) {
+                *file = compiled.next();
+            }
         }
 
-        yield_now().await;
+        let files = files.into_iter().flatten().collect::<Vec<_>>();
 
-        let files = join_all(handles)
-            .await
-            .into_iter()
-            .collect::<Result<Result<Vec<_>>, _>>()??;
+        check_server_file_names(files.iter().map(|file| (&*file.path, &*file.api)))?;
 
-        let file_apis = files.iter().map(|v| v.2.clone()).collect::<Vec<_>>();
+        let project_apis = ProjectApis {
+            files: files.iter().map(|v| v.api.clone()).collect(),
+        };
 
-        let project_apis = ProjectApis { files: file_apis };
+        if let Some(snapshot) = &self.snapshot {
+            if self.locked {
+                let changes = diff_projects(&load_api_def(snapshot)?, &project_apis);
 
-        {
-            let node_client = print(
-                env.cm.clone(),
-                &fnapi_client_gen::JsClientConfig {
-                    target_env: JsTargetEnv::NodeJs,
+                if !changes.is_empty() {
+                    print_changes(&changes);
+                    bail!(
+                        "api surface differs from the snapshot `{}`",
+                        snapshot.display()
+                    );
                 }
-                .generate(env, &project_apis)?,
-            );
-            write(&fnapi_dir.join("client.node.mjs"), node_client.as_bytes())
-                .context("failed to write node client")?;
+            } else {
+                write_api_manifest(snapshot, &project_apis)?;
+            }
         }
 
+        write_api_manifest(&fnapi_dir.join("api.json"), &project_apis)?;
+
         {
-            let web_client = print(
-                env.cm.clone(),
-                &fnapi_client_gen::JsClientConfig {
-                    target_env: JsTargetEnv::Web,
+            let client_target_dir = self
+                .client_target_dir
+                .clone()
+                .unwrap_or_else(|| fnapi_dir.clone());
+            create_dir_all(&client_target_dir)
+                .context("failed to create client target directory")?;
+
+            let mut client_types = self.client_types.clone();
+            if client_types.is_empty() {
+                client_types = vec![ClientType::Node, ClientType::Web];
+            }
+
+            let js_client = |target_env, comments: &SingleThreadedComments| {
+                fnapi_client_gen::JsClientConfig {
+                    target_env,
+                    route_prefix: config.route_prefix(),
                 }
-                .generate(env, &project_apis)?,
-            );
-            write(&fnapi_dir.join("client.web.mjs"), web_client.as_bytes())
-                .context("failed to write web client")?;
+                .generate(env, &project_apis, comments)
+            };
+
+            for client_type in client_types {
+                // JSDoc comments of the api functions.
+                let comments = SingleThreadedComments::default();
+                let client = match client_type {
+                    ClientType::Node => js_client(JsTargetEnv::NodeJs, &comments)?,
+                    ClientType::Web => js_client(JsTargetEnv::Web, &comments)?,
+                    ClientType::Dts => {
+                        DtsClientConfig::default().generate(env, &project_apis, &comments)?
+                    }
+                };
+
+                let path = client_target_dir.join(client_type.file_name());
+                write(
+                    &path,
+                    print(env.cm.clone(), Some(&comments), &client).as_bytes(),
+                )
+                .with_context(|| format!("failed to write client `{}`", path.display()))?;
+            }
+        }
+
+        if self.openapi {
+            write_openapi(
+                &fnapi_dir.join("openapi.json"),
+                &OpenApiConfig {
+                    route_prefix: config.route_prefix(),
+                    ..Default::default()
+                },
+                &project_apis,
+            )?;
         }
 
         files
             .par_iter()
-            .map(|(_, m, api)| {
-                write(
-                    &fnapi_server_dir.join(format!("{}.mjs", api.class_name)),
-                    print(env.cm.clone(), m).as_bytes(),
-                )
-                .context("failed to write web client")
-            })
+            .map(|file| write_server_file(&fnapi_server_dir, file))
             .collect::<Result<Vec<_>>>()?;
 
+        if server_target == Target::Native {
+            write_server_entry(&fnapi_server_dir, files.iter().map(|file| &*file.api))?;
+        }
+
         Ok(())
     }
+
+    /// Fills options which are not passed as flags using `config`.
+    fn merge_config(&mut self, config: &FnApiConfig) {
+        if self.include.is_empty() {
+            self.include = config.include.clone();
+        }
+        self.server_target = self.server_target.or(config.server_target);
+        if self.client_types.is_empty() {
+            self.client_types = config.client_types.clone();
+        }
+        if self.client_target_dir.is_none() {
+            self.client_target_dir = config.client_target_dir.clone();
+        }
+        if self.fnapi_dir.is_none() {
+            self.fnapi_dir = config.fnapi_dir.clone();
+        }
+    }
 }
 
-fn expand_dir(p: PathBuf) -> Result<Vec<PathBuf>> {
-    let stat = p
-        .metadata()
-        .with_context(|| format!("failed to get metadata of `{}`", p.display()))?;
+/// Api files of a command, selected from the files of a `tsconfig.json` or
+/// from the input paths.
+pub(super) struct ApiInputs {
+    pub tsconfig_json: Option<PathBuf>,
+
+    /// Directories or files, which are used if `tsconfig_json` is [None].
+    pub inputs: Vec<PathBuf>,
 
-    if stat.is_dir() {
-        let read = p
-            .read_dir()
-            .with_context(|| format!("failed to read directory `{}`", p.display()))?;
+    /// The project of `tsconfig_json`, which is resolved to list its files.
+    pub project: Option<Arc<Project>>,
 
-        Ok(read
-            .par_bridge()
-            .map(|v| expand_dir(v?.path()))
-            .collect::<Result<Vec<_>>>()?
-            .into_iter()
-            .flatten()
-            .collect())
-    } else {
-        Ok(vec![p])
+    pub api_files: Vec<PathBuf>,
+}
+
+impl ApiInputs {
+    /// Selects api files of `tsconfig_json`, or of `inputs` if it's [None].
+    ///
+    /// Both of them are read from `config` if they are not passed as flags.
+    pub async fn select(
+        env: &Env,
+        config: &FnApiConfig,
+        tsconfig_json: Option<PathBuf>,
+        inputs: Vec<PathBuf>,
+        filter: &InputFilter,
+        server_target: Target,
+    ) -> Result<Self> {
+        let (tsconfig_json, inputs) = if tsconfig_json.is_none() && inputs.is_empty() {
+            (config.project.clone(), config.inputs.clone())
+        } else {
+            (tsconfig_json, inputs)
+        };
+
+        match tsconfig_json {
+            Some(tsconfig_json) => {
+                let project = resolve_project(
+                    env,
+                    config,
+                    InputFiles::TsConfig(tsconfig_json.clone()),
+                    server_target,
+                )
+                .await?;
+                let api_files = filter_api_files(select_project_files(
+                    &tsconfig_json,
+                    &project.files,
+                    filter,
+                )?)?;
+
+                Ok(Self {
+                    tsconfig_json: Some(tsconfig_json),
+                    inputs: vec![],
+                    project: Some(project),
+                    api_files,
+                })
+            }
+            None => Ok(Self {
+                tsconfig_json: None,
+                api_files: expand_inputs(inputs.clone(), filter)?,
+                inputs,
+                project: None,
+            }),
+        }
+    }
+
+    /// Fails if there's no api file.
+    pub fn non_empty(self) -> Result<Self> {
+        if self.api_files.is_empty() {
+            match &self.tsconfig_json {
+                Some(tsconfig_json) => bail!(
+                    "no api files found in the project `{}`",
+                    tsconfig_json.display()
+                ),
+                None => bail!("no inputs found"),
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Returns the project of the `tsconfig.json`, or a project of the api
+    /// files.
+    pub async fn into_project(
+        self,
+        env: &Env,
+        config: &FnApiConfig,
+        server_target: Target,
+    ) -> Result<(Arc<Project>, Vec<PathBuf>)> {
+        let project = match self.project {
+            Some(project) => project,
+            None => {
+                resolve_project(
+                    env,
+                    config,
+                    InputFiles::Files(self.api_files.clone()),
+                    server_target,
+                )
+                .await?
+            }
+        };
+
+        Ok((project, self.api_files))
     }
 }
 
-fn print(cm: Arc<SourceMap>, m: &Module) -> String {
+async fn resolve_project(
+    env: &Env,
+    config: &FnApiConfig,
+    input: InputFiles,
+    server_target: Target,
+) -> Result<Arc<Project>> {
+    ProjectConfig {
+        input: Arc::new(input),
+        route_prefix: config.route_prefix(),
+    }
+    .resolve(env, server_target.to_server_target())
+    .await
+    .context("failed to resolve project")
+}
+
+/// An api file compiled by [ServerApiFile::process].
+pub(crate) struct CompiledFile {
+    pub path: PathBuf,
+
+    /// Printed server module.
+    pub code: String,
+    pub source_map: String,
+
+    pub api: Arc<ApiFile>,
+}
+
+/// Result of [compile_files].
+pub(super) struct CompileSummary {
+    pub compiled: Vec<CompiledFile>,
+    pub failed: Vec<PathBuf>,
+}
+
+impl CompileSummary {
+    /// Returns the compiled files, or prints the failed files and returns an
+    /// error if any file failed.
+    pub fn finish(self) -> Result<Vec<CompiledFile>> {
+        if self.failed.is_empty() {
+            return Ok(self.compiled);
+        }
+
+        eprintln!(
+            "Compiled {} file(s), {} failed:",
+            self.compiled.len(),
+            self.failed.len()
+        );
+        for path in &self.failed {
+            eprintln!("  {}", path.display());
+        }
+
+        bail!(
+            "failed to compile {} of {} file(s)",
+            self.failed.len(),
+            self.compiled.len() + self.failed.len()
+        )
+    }
+}
+
+/// Compiles all `files` in parallel.
+///
+/// A failing file does not stop the compilation of other files, and errors of
+/// all files are reported using the handler of `env`.
+pub(super) async fn compile_files(
+    env: &Env,
+    project: &Arc<Project>,
+    files: &[PathBuf],
+) -> CompileSummary {
+    let mut handles = vec![];
+
+    for file in files {
+        let file = file.clone();
+        let project = project.clone();
+        let env = env.clone();
+
+        handles.push(spawn(async move {
+            compile_file(&env, project, file.clone())
+                .await
+                .with_context(|| format!("failed to compile `{}`", file.display()))
+        }));
+    }
+
+    yield_now().await;
+
+    let mut summary = CompileSummary {
+        compiled: vec![],
+        failed: vec![],
+    };
+
+    for (path, res) in files.iter().zip(join_all(handles).await) {
+        match res.map_err(From::from).and_then(|v| v) {
+            Ok(Some(file)) => summary.compiled.push(file),
+            Ok(None) => summary.failed.push(path.clone()),
+            Err(err) => {
+                env.handler.struct_err(&format!("{:?}", err)).emit();
+                summary.failed.push(path.clone());
+            }
+        }
+    }
+
+    summary
+}
+
+/// Compiles `path`.
+///
+/// Returns [None] if the file has any error, which is reported using the
+/// handler of `env`.
+pub(super) async fn compile_file(
+    env: &Env,
+    project: Arc<Project>,
+    path: PathBuf,
+) -> Result<Option<CompiledFile>> {
+    // Errors are counted per file, as files are compiled concurrently.
+    let file_env = Env {
+        handler: Arc::new(Handler::with_emitter(
+            true,
+            false,
+            box ForwardingEmitter(env.handler.clone()),
+        )),
+        ..env.clone()
+    };
+
+    let m = ServerApiFile::from_file(path.clone())?;
+    let (module, api) = m.process(&file_env, project).await?;
+
+    if file_env.handler.has_errors() {
+        return Ok(None);
+    }
+
+    let (code, source_map) = print_with_source_map(env.cm.clone(), &module)?;
+
+    Ok(Some(CompiledFile {
+        path,
+        code,
+        source_map,
+        api,
+    }))
+}
+
+/// Emits diagnostics using another handler.
+struct ForwardingEmitter(Arc<Handler>);
+
+impl Emitter for ForwardingEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        DiagnosticBuilder::new_diagnostic(&self.0, (**db).clone()).emit();
+    }
+}
+
+/// Returns the name of the server module for `api`, which is named after its
+/// first api class.
+///
+/// Returns [None] if `api` does not have any api class.
+pub(super) fn server_file_name(api: &ApiFile) -> Option<String> {
+    api.classes
+        .first()
+        .map(|class| format!("{}.mjs", class.class_name))
+}
+
+/// Returns an error if two api files would be written to the same server
+/// module, which happens if their first api classes, or the files of exported
+/// api functions, have the same name.
+pub(super) fn check_server_file_names<'a>(
+    apis: impl Iterator<Item = (&'a Path, &'a ApiFile)>,
+) -> Result<()> {
+    let mut seen = HashMap::<String, &Path>::new();
+
+    for (path, api) in apis {
+        let file_name = match server_file_name(api) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        if let Some(prev) = seen.insert(file_name.clone(), path) {
+            bail!(
+                "`{}` and `{}` are both compiled to the server module `{}`; rename the first api \
+                 class or the file of one of them",
+                prev.display(),
+                path.display(),
+                file_name
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the server module, `{ClassName}.mjs`, and its source map,
+/// `{ClassName}.mjs.map`, to `server_dir`.
+pub(super) fn write_server_file(server_dir: &Path, file: &CompiledFile) -> Result<()> {
+    let file_name = match server_file_name(&file.api) {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+    let map_file_name = format!("{}.map", file_name);
+
+    let code = format!("{}//# sourceMappingURL={}\n", file.code, map_file_name);
+
+    write(&server_dir.join(&map_file_name), &file.source_map).with_context(|| {
+        format!(
+            "failed to write source map of server file for `{}`",
+            file.path.display()
+        )
+    })?;
+    write(&server_dir.join(&file_name), code.as_bytes())
+        .with_context(|| format!("failed to write server file for `{}`", file.path.display()))
+}
+
+/// Writes `project_apis` as an [ApiManifest].
+fn write_api_manifest(path: &Path, project_apis: &ProjectApis) -> Result<()> {
+    let manifest = ApiManifest::new(project_apis.clone());
+
+    write(path, serde_json::to_string_pretty(&manifest)?)
+        .with_context(|| format!("failed to write api definition to `{}`", path.display()))
+}
+
+/// Writes `index.mjs` to `server_dir`, which starts a server with all api
+/// classes.
+///
+/// The server listens on `HOST` and `PORT` from the environment.
+///
+/// Returns the path to the written file.
+pub(super) fn write_server_entry<'a>(
+    server_dir: &Path,
+    apis: impl Iterator<Item = &'a ApiFile>,
+) -> Result<PathBuf> {
+    let mut imports = vec![];
+    let mut class_names = vec![];
+
+    for api in apis {
+        let file_name = match server_file_name(api) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let mut specifiers = vec![];
+        let mut named = vec![];
+        for class in &api.classes {
+            if class.is_default_export {
+                specifiers.push(class.class_name.to_string());
+            } else {
+                named.push(class.class_name.to_string());
+            }
+        }
+        if !named.is_empty() {
+            specifiers.push(format!("{{ {} }}", named.join(", ")));
+        }
+
+        imports.push(format!(
+            "import {} from \"./{}\";\n",
+            specifiers.join(", "),
+            file_name
+        ));
+        class_names.extend(api.classes.iter().map(|class| class.class_name.to_string()));
+    }
+    let imports = imports.concat();
+
+    let code = format!(
+        "import createServer from \"@fnapi/api/rt/createServer.js\";\n{}\nconst server = \
+         createServer({{ logger: true }}, [{}]);\nawait server.listen(parseInt(process.env.PORT \
+         ?? \"3000\"), process.env.HOST ?? \"127.0.0.1\");\n",
+        imports,
+        class_names.join(", ")
+    );
+
+    let path = server_dir.join("index.mjs");
+    write(&path, code).context("failed to write the entry of the server")?;
+
+    Ok(path)
+}
+
+pub(super) fn write_openapi(
+    path: &Path,
+    config: &OpenApiConfig,
+    project_apis: &ProjectApis,
+) -> Result<()> {
+    let doc = config
+        .generate(project_apis)
+        .context("failed to generate OpenAPI document")?;
+
+    write(path, serde_json::to_string_pretty(&doc)?)
+        .with_context(|| format!("failed to write OpenAPI document to `{}`", path.display()))
+}
+
+/// Finds api files in `inputs`.
+///
+/// See [walk_inputs] for the files considered, and
+/// [ServerApiFile::is_api_file] for what counts as an api file.
+pub(super) fn expand_inputs(inputs: Vec<PathBuf>, filter: &InputFilter) -> Result<Vec<PathBuf>> {
+    filter_api_files(walk_inputs(inputs, filter)?)
+}
+
+/// Returns all source files in `inputs` which are selected by `filter`.
+///
+/// Directories are walked recursively, skipping `node_modules`, hidden files
+/// and paths ignored by `.gitignore`.
+pub(super) fn walk_inputs(inputs: Vec<PathBuf>, filter: &InputFilter) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+
+    for input in inputs {
+        let walker = WalkBuilder::new(&input)
+            .require_git(false)
+            .filter_entry(|entry| entry.file_name() != "node_modules")
+            .build();
+
+        for entry in walker {
+            let entry =
+                entry.with_context(|| format!("failed to read input `{}`", input.display()))?;
+            let path = entry.path();
+
+            if entry.file_type().map_or(false, |ty| ty.is_file())
+                && is_source_file(path)
+                && filter.is_match(path)
+            {
+                files.push(path.to_path_buf());
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Selects api files among `files`, in parallel.
+pub(super) fn filter_api_files(files: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+    Ok(files
+        .into_par_iter()
+        .map(|path| Ok(ServerApiFile::is_api_file(&path)?.then(|| path)))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+fn is_source_file(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+
+    SOURCE_FILE_EXTENSIONS.iter().any(|ext| name.ends_with(ext)) && !name.ends_with(".d.ts")
+}
+
+/// Selects source files among `files` of the project `tsconfig_json`, which
+/// may be api files.
+///
+/// Globs are matched against paths relative to the directory of
+/// `tsconfig_json`.
+pub(super) fn select_project_files(
+    tsconfig_json: &Path,
+    files: &[PathBuf],
+    filter: &InputFilter,
+) -> Result<Vec<PathBuf>> {
+    let root = tsconfig_json
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .canonicalize()
+        .with_context(|| {
+            format!(
+                "failed to get the directory of `{}`",
+                tsconfig_json.display()
+            )
+        })?;
+
+    Ok(files
+        .iter()
+        .filter(|path| {
+            let rel = path.strip_prefix(&root).unwrap_or(path);
+
+            !rel.components().any(|c| c.as_os_str() == "node_modules") && filter.is_match(rel)
+        })
+        .cloned()
+        .collect())
+}
+
+/// Prints `m` with a source map which maps to the original files.
+fn print_with_source_map(cm: Arc<SourceMap>, m: &Module) -> Result<(String, String)> {
     let mut buf = vec![];
+    let mut mappings = vec![];
 
     {
         let mut emitter = swc_ecmascript::codegen::Emitter {
             cfg: Default::default(),
             cm: cm.clone(),
             comments: None,
+            wr: JsWriter::new(cm.clone(), "\n", &mut buf, Some(&mut mappings)),
+        };
+
+        emitter.emit_module(m).context("failed to print module")?;
+    }
+
+    let mut map = vec![];
+    cm.build_source_map_with_config(&mut mappings, None, SourceMapConfig)
+        .to_writer(&mut map)
+        .context("failed to write source map")?;
+
+    Ok((
+        String::from_utf8(buf).unwrap(),
+        String::from_utf8(map).unwrap(),
+    ))
+}
+
+/// Uses absolute paths for sources, because source maps are not written next
+/// to the original files.
+struct SourceMapConfig;
+
+impl SourceMapGenConfig for SourceMapConfig {
+    fn file_name_to_source(&self, f: &FileName) -> String {
+        match f {
+            FileName::Real(path) => path
+                .canonicalize()
+                .unwrap_or_else(|_| path.clone())
+                .display()
+                .to_string(),
+            _ => f.to_string(),
+        }
+    }
+}
+
+pub(super) fn print(cm: Arc<SourceMap>, comments: Option<&dyn Comments>, m: &Module) -> String {
+    let mut buf = vec![];
+
+    {
+        let mut emitter = swc_ecmascript::codegen::Emitter {
+            cfg: Default::default(),
+            cm: cm.clone(),
+            comments,
             wr: JsWriter::new(cm, "\n", &mut buf, None),
         };
 
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use fnapi_core::Env;
+
+use super::build::{compile_files, ApiInputs, Target};
+use crate::config::FnApiConfig;
+
+/// Validate api files without emitting anything.
+#[derive(Parser, Debug)]
+pub(crate) struct CheckCommand {
+    /// Directories or files to check
+    inputs: Vec<PathBuf>,
+
+    /// Check api files of a `tsconfig.json` instead of `inputs`
+    #[clap(long, short = 'p', conflicts_with = "inputs")]
+    project: Option<PathBuf>,
+
+    /// Server target to validate api files for. Defaults to `fnapi`
+    #[clap(arg_enum, long, short = 't')]
+    server_target: Option<Target>,
+}
+
+impl CheckCommand {
+    pub async fn run(self, env: &Env, config: &FnApiConfig) -> Result<()> {
+        let server_target = self
+            .server_target
+            .or(config.server_target)
+            .unwrap_or_default();
+
+        let (project, api_files) = ApiInputs::select(
+            env,
+            config,
+            self.project,
+            self.inputs,
+            &config.input_filter()?,
+            server_target,
+        )
+        .await?
+        .non_empty()?
+        .into_project(env, config, server_target)
+        .await?;
+
+        compile_files(env, &project, &api_files).await.finish()?;
+
+        Ok(())
+    }
+}
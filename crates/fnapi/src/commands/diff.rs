@@ -0,0 +1,154 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use fnapi_api_def::{
+    diff::{diff_projects, ApiChange, ChangeKind},
+    ApiFile, ProjectApis, SCHEMA_VERSION,
+};
+use fnapi_core::Env;
+use serde_json::{json, Value};
+
+/// Compare two api definitions and report breaking changes.
+#[derive(Parser, Debug)]
+pub(crate) struct DiffCommand {
+    /// The old api definition
+    old: PathBuf,
+
+    /// The new api definition
+    new: PathBuf,
+}
+
+impl DiffCommand {
+    pub async fn run(self, _env: &Env) -> Result<()> {
+        let old = load_api_def(&self.old)?;
+        let new = load_api_def(&self.new)?;
+
+        let changes = diff_projects(&old, &new);
+        print_changes(&changes);
+
+        let breaking = changes
+            .iter()
+            .filter(|c| c.kind == ChangeKind::Breaking)
+            .count();
+        if breaking > 0 {
+            bail!("found {} breaking change(s)", breaking);
+        }
+
+        Ok(())
+    }
+}
+
+/// Loads a serialized [ApiManifest](fnapi_api_def::ApiManifest),
+/// [ProjectApis] or [ApiFile].
+pub(super) fn load_api_def(path: &Path) -> Result<ProjectApis> {
+    let s = read_to_string(path)
+        .with_context(|| format!("failed to read api definition `{}`", path.display()))?;
+
+    parse_api_def(path, &s)
+}
+
+fn parse_api_def(path: &Path, s: &str) -> Result<ProjectApis> {
+    let mut json: Value = serde_json::from_str(s)
+        .with_context(|| format!("failed to parse api definition `{}`", path.display()))?;
+
+    if let Some(version) = json.get("schemaVersion").cloned() {
+        match version.as_u64() {
+            Some(1) => migrate_v1(&mut json),
+            Some(v) if v == SCHEMA_VERSION as u64 => {}
+            _ => bail!(
+                "`{}` has an unsupported schema version `{}`; regenerate it with `fnapi build`",
+                path.display(),
+                version
+            ),
+        }
+    }
+
+    if json.get("files").is_some() {
+        serde_json::from_value(json)
+            .with_context(|| format!("`{}` is not a valid project api", path.display()))
+    } else {
+        let file: ApiFile = serde_json::from_value(json)
+            .with_context(|| format!("`{}` is not a valid api file", path.display()))?;
+
+        Ok(ProjectApis {
+            files: vec![Arc::new(file)],
+        })
+    }
+}
+
+/// Converts a manifest of version 1, where each file was a single class like
+/// `{ "className": "TodoApi", "functions": [] }`, to the current version.
+fn migrate_v1(json: &mut Value) {
+    let files = match json.get_mut("files").and_then(Value::as_array_mut) {
+        Some(files) => files,
+        None => return,
+    };
+
+    for file in files {
+        if file.get("className").is_some() {
+            *file = json!({ "classes": [file.take()] });
+        }
+    }
+}
+
+pub(super) fn print_changes(changes: &[ApiChange]) {
+    for change in changes {
+        println!("{}", change);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::parse_api_def;
+
+    #[test]
+    fn load_v1_manifest() {
+        let apis = parse_api_def(
+            Path::new("api.json"),
+            r#"{
+                "schemaVersion": 1,
+                "files": [
+                    {
+                        "className": "TodoApi",
+                        "functions": [
+                            {
+                                "name": "get",
+                                "params": [
+                                    {
+                                        "name": "id",
+                                        "ty": { "kind": "keyword", "keyword": "string" }
+                                    }
+                                ],
+                                "returnType": { "kind": "keyword", "keyword": "string" }
+                            }
+                        ]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let classes = apis.classes().collect::<Vec<_>>();
+        assert_eq!(classes.len(), 1);
+        assert_eq!(&*classes[0].class_name, "TodoApi");
+        assert_eq!(classes[0].functions[0].request_params()[0].0, "id");
+    }
+
+    #[test]
+    fn reject_unknown_version() {
+        let err = parse_api_def(
+            Path::new("api.json"),
+            r#"{ "schemaVersion": 100, "files": [] }"#,
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("regenerate"));
+    }
+}
@@ -0,0 +1,490 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::Result;
+use clap::Parser;
+use fnapi_api_def::{types::json_schema::ToJsonSchema, ApiFile, ApiFn};
+use fnapi_compiler::{project::Project, ServerApiFile};
+use fnapi_core::Env;
+use serde_json::Value;
+use swc_atoms::JsWord;
+use swc_common::{errors::Handler, FileName, SourceMap, Span};
+use swc_ecmascript::{
+    ast::{
+        ClassDecl, ClassExpr, ClassMember, Decl, DefaultDecl, EsVersion, ExportDecl,
+        ExportDefaultDecl, FnDecl, ModuleDecl, ModuleItem, Pat, PropName,
+    },
+    parser::{parse_file_as_module, Syntax, TsConfig},
+};
+use tokio::io::{stdin, stdout};
+use tower_lsp::{
+    jsonrpc,
+    lsp_types::{
+        CodeLens, CodeLensOptions, CodeLensParams, Command, Diagnostic,
+        DiagnosticRelatedInformation, DiagnosticSeverity, DidChangeTextDocumentParams,
+        DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams, Hover,
+        HoverContents, HoverParams, HoverProviderCapability, InitializeParams, InitializeResult,
+        Location, MarkupContent, MarkupKind, Position, Range, ServerCapabilities, ServerInfo,
+        TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+    },
+    Client, LanguageServer, LspService, Server,
+};
+use tracing::error;
+
+use super::build::{compile_file, ApiInputs};
+use crate::{
+    config::FnApiConfig,
+    diagnostics::{utf16_col, CollectedDiagnostics, DiagnosticRecord, SpanRecord},
+};
+
+/// Start a language server for api files, using stdio.
+///
+/// Documents are compiled when they are opened or changed, including unsaved
+/// changes.
+#[derive(Parser, Debug)]
+pub(crate) struct LspCommand {}
+
+impl LspCommand {
+    pub async fn run(self, env: &Env, config: &FnApiConfig) -> Result<()> {
+        let server_target = config.server_target.unwrap_or_default();
+
+        // Opened documents are added to the project when they are compiled.
+        let (project, _) = ApiInputs::select(
+            env,
+            config,
+            None,
+            vec![],
+            &config.input_filter()?,
+            server_target,
+        )
+        .await?
+        .into_project(env, config, server_target)
+        .await?;
+
+        let (service, socket) = LspService::new(|client| Backend {
+            client,
+            env: env.clone(),
+            project,
+            documents: Default::default(),
+        });
+
+        Server::new(stdin(), stdout(), socket).serve(service).await;
+
+        Ok(())
+    }
+}
+
+struct Backend {
+    client: Client,
+    env: Env,
+    project: Arc<Project>,
+    documents: Mutex<HashMap<Url, Document>>,
+}
+
+#[derive(Default)]
+struct Document {
+    text: String,
+
+    /// Api of the last successful compilation.
+    api: Option<Arc<ApiFile>>,
+}
+
+/// An api method in a document.
+struct MethodLocation {
+    name_range: Range,
+    route: String,
+    api: Arc<ApiFn>,
+}
+
+impl Backend {
+    /// Compiles the latest text of `uri` and publishes the diagnostics.
+    async fn check(&self, uri: Url) {
+        let path = match uri.to_file_path() {
+            Ok(path) => path,
+            Err(()) => return,
+        };
+        let text = match self.documents.lock().unwrap().get(&uri) {
+            Some(doc) => doc.text.clone(),
+            None => return,
+        };
+
+        // Helper modules and other non-api files are not compiled.
+        if !ServerApiFile::is_api_source(&path, text.clone()) {
+            self.client.publish_diagnostics(uri, vec![], None).await;
+            return;
+        }
+
+        let collected = CollectedDiagnostics::default();
+        let env = Env {
+            handler: Arc::new(Handler::with_emitter(
+                true,
+                false,
+                box collected.emitter(self.env.cm.clone()),
+            )),
+            ..self.env.clone()
+        };
+
+        let res = match self.project.update(&env, &path, &text).await {
+            Ok(()) => compile_file(&env, self.project.clone(), path).await,
+            Err(err) => Err(err),
+        };
+
+        // A newer text is checked by another call.
+        let is_latest = self
+            .documents
+            .lock()
+            .unwrap()
+            .get(&uri)
+            .map_or(false, |doc| doc.text == text);
+        if !is_latest {
+            return;
+        }
+
+        let mut diagnostics = collected
+            .take()
+            .iter()
+            .map(to_lsp_diagnostic)
+            .collect::<Vec<_>>();
+
+        match res {
+            Ok(Some(compiled)) => {
+                if let Some(doc) = self.documents.lock().unwrap().get_mut(&uri) {
+                    doc.api = Some(compiled.api);
+                }
+            }
+            Ok(None) => {}
+            Err(err) => {
+                diagnostics.push(Diagnostic::new_simple(
+                    Range::default(),
+                    format!("{:?}", err),
+                ));
+            }
+        }
+
+        self.client
+            .publish_diagnostics(uri, diagnostics, None)
+            .await;
+    }
+
+    /// Returns api methods in the document, using the latest text.
+    fn methods(&self, uri: &Url) -> Vec<MethodLocation> {
+        let path = match uri.to_file_path() {
+            Ok(path) => path,
+            Err(()) => return vec![],
+        };
+
+        let doc = self
+            .documents
+            .lock()
+            .unwrap()
+            .get(uri)
+            .and_then(|doc| Some((doc.text.clone(), doc.api.clone()?)));
+        let (text, api) = match doc {
+            Some(v) => v,
+            None => return vec![],
+        };
+
+        find_method_names(&path, &text)
+            .into_iter()
+            .filter_map(|(class_name, name, name_range)| {
+                let class = api.classes.iter().find(|c| c.class_name == class_name)?;
+                let f = class.functions.iter().find(|f| f.name == name)?;
+
+                Some(MethodLocation {
+                    name_range,
+                    route: f.route(self.project.route_prefix.as_deref(), &class.class_name),
+                    api: f.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, _: InitializeParams) -> jsonrpc::Result<InitializeResult> {
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                    TextDocumentSyncKind::FULL,
+                )),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                code_lens_provider: Some(CodeLensOptions {
+                    resolve_provider: Some(false),
+                }),
+                ..Default::default()
+            },
+            server_info: Some(ServerInfo {
+                name: "fnapi".into(),
+                version: Some(env!("CARGO_PKG_VERSION").into()),
+            }),
+        })
+    }
+
+    async fn shutdown(&self) -> jsonrpc::Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let uri = params.text_document.uri;
+
+        self.documents.lock().unwrap().insert(
+            uri.clone(),
+            Document {
+                text: params.text_document.text,
+                api: None,
+            },
+        );
+
+        self.check(uri).await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // We use full sync, so the last change contains the whole document.
+        if let Some(change) = params.content_changes.into_iter().last() {
+            if let Some(doc) = self
+                .documents
+                .lock()
+                .unwrap()
+                .get_mut(&params.text_document.uri)
+            {
+                doc.text = change.text;
+            }
+        }
+
+        self.check(params.text_document.uri).await;
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        self.check(params.text_document.uri).await;
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+
+        self.documents.lock().unwrap().remove(&uri);
+
+        // Unsaved changes are discarded, so the file is read from the disk.
+        if let Ok(path) = uri.to_file_path() {
+            if let Err(err) = self.project.invalidate(&[path]).await {
+                error!("{:?}", err);
+            }
+        }
+
+        self.client.publish_diagnostics(uri, vec![], None).await;
+    }
+
+    async fn hover(&self, params: HoverParams) -> jsonrpc::Result<Option<Hover>> {
+        let pos = params.text_document_position_params.position;
+
+        let method = self
+            .methods(&params.text_document_position_params.text_document.uri)
+            .into_iter()
+            .find(|method| contains(&method.name_range, pos));
+
+        Ok(method.map(|method| Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: describe_method(&method),
+            }),
+            range: Some(method.name_range),
+        }))
+    }
+
+    async fn code_lens(&self, params: CodeLensParams) -> jsonrpc::Result<Option<Vec<CodeLens>>> {
+        let lenses = self
+            .methods(&params.text_document.uri)
+            .into_iter()
+            .map(|method| CodeLens {
+                range: method.name_range,
+                command: Some(Command {
+                    title: format!("{} {}", method.api.method.as_str(), method.route),
+                    command: String::new(),
+                    arguments: None,
+                }),
+                data: None,
+            })
+            .collect();
+
+        Ok(Some(lenses))
+    }
+}
+
+/// Finds methods of exported classes and exported functions in `text`, as
+/// `(class name, method name, range)`. Functions use the name of the file as
+/// the class name.
+///
+/// This parses `text` instead of using the compiled module, so the result
+/// follows unsaved changes.
+fn find_method_names(path: &Path, text: &str) -> Vec<(JsWord, JsWord, Range)> {
+    let cm = SourceMap::default();
+    let fm = cm.new_source_file(FileName::Real(path.to_path_buf()), text.to_string());
+
+    let m = match parse_file_as_module(
+        &fm,
+        Syntax::Typescript(TsConfig {
+            tsx: path.to_string_lossy().ends_with(".tsx"),
+            decorators: true,
+            ..Default::default()
+        }),
+        EsVersion::latest(),
+        None,
+        &mut vec![],
+    ) {
+        Ok(m) => m,
+        Err(_) => return vec![],
+    };
+
+    let mut names = m
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                decl:
+                    DefaultDecl::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    }),
+                ..
+            })) => Some((ident, class)),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Class(ClassDecl { ident, class, .. }),
+                ..
+            })) => Some((ident, class)),
+            _ => None,
+        })
+        .flat_map(|(ident, class)| {
+            class.body.iter().filter_map(|member| match member {
+                ClassMember::Method(method) => match &method.key {
+                    PropName::Ident(key) => {
+                        Some((ident.sym.clone(), key.sym.clone(), to_range(&cm, key.span)))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let group_name: JsWord = ServerApiFile::function_group_name(path).into();
+    for item in &m.body {
+        let idents = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Fn(FnDecl { ident, .. }),
+                ..
+            })) => vec![ident],
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Var(var),
+                ..
+            })) => var
+                .decls
+                .iter()
+                .filter_map(|decl| match &decl.name {
+                    Pat::Ident(i) => Some(&i.id),
+                    _ => None,
+                })
+                .collect(),
+            _ => continue,
+        };
+
+        names.extend(idents.into_iter().map(|ident| {
+            (
+                group_name.clone(),
+                ident.sym.clone(),
+                to_range(&cm, ident.span),
+            )
+        }));
+    }
+
+    names
+}
+
+fn describe_method(method: &MethodLocation) -> String {
+    let params = Value::Array(
+        method
+            .api
+            .params
+            .iter()
+            .map(|param| Value::Object(param.to_json_schema()))
+            .collect(),
+    );
+    let return_type = Value::Object(method.api.return_type.to_json_schema());
+
+    format!(
+        "**{}** `{}`\n\nParameters:\n\n```json\n{}\n```\n\nReturns:\n\n```json\n{}\n```",
+        method.api.method.as_str(),
+        method.route,
+        serde_json::to_string_pretty(&params).unwrap(),
+        serde_json::to_string_pretty(&return_type).unwrap(),
+    )
+}
+
+fn to_lsp_diagnostic(record: &DiagnosticRecord) -> Diagnostic {
+    let mut message = record.message.clone();
+    for help in &record.help {
+        message.push_str("\nhelp: ");
+        message.push_str(help);
+    }
+
+    let related_information = record
+        .notes
+        .iter()
+        .filter_map(|note| {
+            let uri = Url::from_file_path(PathBuf::from(note.file.as_ref()?)).ok()?;
+
+            Some(DiagnosticRelatedInformation {
+                location: Location {
+                    uri,
+                    range: span_record_to_range(note.span.as_ref()?),
+                },
+                message: note.message.clone(),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Diagnostic {
+        range: record
+            .span
+            .as_ref()
+            .map(span_record_to_range)
+            .unwrap_or_default(),
+        severity: Some(match record.level {
+            "error" => DiagnosticSeverity::ERROR,
+            "warning" => DiagnosticSeverity::WARNING,
+            _ => DiagnosticSeverity::INFORMATION,
+        }),
+        source: Some("fnapi".into()),
+        message,
+        related_information: if related_information.is_empty() {
+            None
+        } else {
+            Some(related_information)
+        },
+        ..Default::default()
+    }
+}
+
+fn to_range(cm: &SourceMap, span: Span) -> Range {
+    let lo = cm.lookup_char_pos(span.lo);
+    let hi = cm.lookup_char_pos(span.hi);
+
+    Range::new(
+        Position::new(lo.line as u32 - 1, utf16_col(&lo) as u32),
+        Position::new(hi.line as u32 - 1, utf16_col(&hi) as u32),
+    )
+}
+
+/// Converts 1-based lines and columns to 0-based ones.
+fn span_record_to_range(span: &SpanRecord) -> Range {
+    Range::new(
+        Position::new(span.start_line as u32 - 1, span.start_column as u32 - 1),
+        Position::new(span.end_line as u32 - 1, span.end_column as u32 - 1),
+    )
+}
+
+fn contains(range: &Range, pos: Position) -> bool {
+    range.start <= pos && pos <= range.end
+}
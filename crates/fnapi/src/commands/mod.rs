@@ -1,4 +1,15 @@
-pub(crate) use self::{build::BuildCommand, serve::ServeCommand};
+pub(crate) use self::{
+    build::{BuildCommand, ClientType, CompiledFile, Target},
+    check::CheckCommand,
+    diff::DiffCommand,
+    lsp::LspCommand,
+    openapi::OpenApiCommand,
+    serve::ServeCommand,
+};
 
 mod build;
+mod check;
+mod diff;
+mod lsp;
+mod openapi;
 mod serve;
@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use fnapi_api_def::ProjectApis;
+use fnapi_core::Env;
+use fnapi_openapi::OpenApiConfig;
+
+use super::build::{compile_files, write_openapi, ApiInputs, Target};
+use crate::config::FnApiConfig;
+
+/// Generate an OpenAPI document for functions.
+#[derive(Parser, Debug)]
+pub(crate) struct OpenApiCommand {
+    /// Directories or files to generate the document for
+    inputs: Vec<PathBuf>,
+
+    /// Generate the document for api files of a `tsconfig.json` instead of
+    /// `inputs`
+    #[clap(long, short = 'p', conflicts_with = "inputs")]
+    project: Option<PathBuf>,
+
+    /// Path to the OpenAPI document. Defaults to `openapi.json`
+    #[clap(long, short = 'o')]
+    output: Option<PathBuf>,
+
+    /// Title of the api.
+    #[clap(long)]
+    title: Option<String>,
+
+    /// Version of the api.
+    #[clap(long)]
+    api_version: Option<String>,
+}
+
+impl OpenApiCommand {
+    pub async fn run(self, env: &Env, config: &FnApiConfig) -> Result<()> {
+        let (project, api_files) = ApiInputs::select(
+            env,
+            config,
+            self.project,
+            self.inputs,
+            &config.input_filter()?,
+            Target::Native,
+        )
+        .await?
+        .non_empty()?
+        .into_project(env, config, Target::Native)
+        .await?;
+
+        let files = compile_files(env, &project, &api_files).await.finish()?;
+
+        let project_apis = ProjectApis {
+            files: files.iter().map(|v| v.api.clone()).collect(),
+        };
+
+        let mut openapi_config = OpenApiConfig {
+            route_prefix: config.route_prefix(),
+            ..Default::default()
+        };
+        if let Some(title) = self.title {
+            openapi_config.title = title;
+        }
+        if let Some(version) = self.api_version {
+            openapi_config.version = version;
+        }
+
+        write_openapi(
+            &self.output.unwrap_or_else(|| PathBuf::from("openapi.json")),
+            &openapi_config,
+            &project_apis,
+        )
+    }
+}
@@ -1,16 +1,281 @@
-use anyhow::Result;
+use std::{
+    collections::BTreeMap,
+    fs::{create_dir_all, remove_file},
+    iter::once,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
 use clap::Parser;
+use fnapi_api_def::ApiFile;
+use fnapi_compiler::{project::Project, ServerApiFile};
 use fnapi_core::Env;
+use tokio::{
+    process::{Child, Command},
+    time::sleep,
+};
+use tracing::info;
+
+use super::build::{
+    check_server_file_names, compile_file, select_project_files, server_file_name, walk_inputs,
+    write_server_entry, write_server_file, ApiInputs, CompiledFile, Target,
+};
+use crate::config::{FnApiConfig, InputFilter};
+
+/// Interval used to check the inputs for modifications.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
 
 /// Start a development server
 #[derive(Parser, Debug)]
 pub(crate) struct ServeCommand {
+    /// Directories or files to serve
+    inputs: Vec<PathBuf>,
+
+    /// Serve api files of a `tsconfig.json` instead of `inputs`
+    #[clap(long, conflicts_with = "inputs")]
+    project: Option<PathBuf>,
+
     #[clap(long, short = 'p', default_value = "4321")]
     port: usize,
+
+    /// FnApi directory for the compiled server. Defaults to `.fnapi`
+    #[clap(long)]
+    fnapi_dir: Option<PathBuf>,
+}
+
+/// State of an input file.
+#[derive(Debug, Default)]
+struct WatchedFile {
+    modified: Option<SystemTime>,
+
+    /// `false` for helper modules, which are not compiled.
+    is_api: bool,
+
+    /// Api of the last successful compilation.
+    api: Option<Arc<ApiFile>>,
 }
 
 impl ServeCommand {
-    pub async fn run(self, env: &Env) -> Result<()> {
-        todo!()
+    pub async fn run(mut self, env: &Env, config: &FnApiConfig) -> Result<()> {
+        if self.fnapi_dir.is_none() {
+            self.fnapi_dir = config.fnapi_dir.clone();
+        }
+        let filter = config.input_filter()?;
+
+        let fnapi_dir = self
+            .fnapi_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(".fnapi"));
+        let fnapi_server_dir = fnapi_dir.join("server");
+
+        create_dir_all(&fnapi_server_dir).context("failed to create fnapi server directory")?;
+
+        let inputs = ApiInputs::select(
+            env,
+            config,
+            self.project.clone(),
+            self.inputs.clone(),
+            &filter,
+            Target::Native,
+        )
+        .await?
+        .non_empty()?;
+        let tsconfig_json = inputs.tsconfig_json.clone();
+        let input_paths = inputs.inputs.clone();
+        let (project, _) = inputs.into_project(env, config, Target::Native).await?;
+
+        let watch = match tsconfig_json {
+            Some(tsconfig_json) => Watch::Project {
+                selected: select_project_files(&tsconfig_json, &project.files, &filter)?,
+                files: project.files.clone(),
+            },
+            None => Watch::Inputs(input_paths, filter),
+        };
+
+        let mut files = BTreeMap::<PathBuf, WatchedFile>::new();
+        let mut server: Option<Child> = None;
+        let mut is_first = true;
+
+        loop {
+            let scanned = watch.scan()?;
+
+            let removed = files
+                .keys()
+                .filter(|path| !scanned.contains_key(*path))
+                .cloned()
+                .collect::<Vec<_>>();
+            let changed = scanned
+                .iter()
+                .filter(|(path, modified)| {
+                    files
+                        .get(*path)
+                        .map_or(true, |file| file.modified != **modified)
+                })
+                .map(|(path, _)| path.clone())
+                .collect::<Vec<_>>();
+
+            if removed.is_empty() && changed.is_empty() {
+                sleep(POLL_INTERVAL).await;
+                continue;
+            }
+
+            // Api files may depend on helper modules, so they are all compiled
+            // again if a helper module is modified.
+            let mut helper_changed = false;
+
+            for path in removed {
+                info!("Removed {}", path.display());
+
+                if let Some(file) = files.remove(&path) {
+                    helper_changed |= !file.is_api;
+
+                    if let Some(api) = file.api {
+                        remove_server_file(&fnapi_server_dir, &api);
+                    }
+                }
+            }
+
+            if !is_first {
+                project.invalidate(&changed).await?;
+            }
+            is_first = false;
+
+            for path in &changed {
+                let file = files.entry(path.clone()).or_default();
+                file.modified = scanned[path];
+                file.is_api =
+                    watch.is_selected(path) && ServerApiFile::is_api_file(path).unwrap_or(false);
+
+                if !file.is_api {
+                    helper_changed = true;
+
+                    if let Some(api) = file.api.take() {
+                        remove_server_file(&fnapi_server_dir, &api);
+                    }
+                }
+            }
+
+            let to_compile = files
+                .iter()
+                .filter(|(path, file)| file.is_api && (helper_changed || changed.contains(path)))
+                .map(|(path, _)| path.clone())
+                .collect::<Vec<_>>();
+
+            for path in to_compile {
+                info!("Compiling {}", path.display());
+
+                if let Some(compiled) = compile(env, &project, path.clone()).await {
+                    // Other api files keep their server modules.
+                    let others = files
+                        .iter()
+                        .filter(|(other, _)| **other != path)
+                        .filter_map(|(other, file)| Some((&**other, file.api.as_deref()?)));
+                    if let Err(err) = check_server_file_names(
+                        others.chain(once((&*compiled.path, &*compiled.api))),
+                    ) {
+                        env.handler.struct_err(&format!("{:?}", err)).emit();
+                        continue;
+                    }
+
+                    write_server_file(&fnapi_server_dir, &compiled)?;
+
+                    if let Some(file) = files.get_mut(&path) {
+                        // The server module is renamed if the first class is renamed.
+                        if let Some(old) = file.api.replace(compiled.api.clone()) {
+                            if server_file_name(&old) != server_file_name(&compiled.api) {
+                                remove_server_file(&fnapi_server_dir, &old);
+                            }
+                        }
+                    }
+                }
+            }
+
+            let entry = write_server_entry(
+                &fnapi_server_dir,
+                files.values().filter_map(|file| file.api.as_deref()),
+            )?;
+
+            if let Some(mut server) = server.take() {
+                let _ = server.kill().await;
+            }
+            server = Some(self.start_server(&entry)?);
+        }
+    }
+
+    fn start_server(&self, entry: &Path) -> Result<Child> {
+        info!(
+            "Starting development server at http://localhost:{}",
+            self.port
+        );
+
+        Command::new("node")
+            .arg("--enable-source-maps")
+            .arg(entry)
+            .env("PORT", self.port.to_string())
+            .kill_on_drop(true)
+            .spawn()
+            .context("failed to start the development server")
+    }
+}
+
+/// Compiles `path`, and reports errors using the handler of `env`.
+///
+/// Returns [None] if the file has any error.
+async fn compile(env: &Env, project: &Arc<Project>, path: PathBuf) -> Option<CompiledFile> {
+    match compile_file(env, project.clone(), path).await {
+        Ok(compiled) => compiled,
+        Err(err) => {
+            env.handler.struct_err(&format!("{:?}", err)).emit();
+            None
+        }
+    }
+}
+
+fn remove_server_file(server_dir: &Path, api: &ApiFile) {
+    if let Some(file_name) = server_file_name(api) {
+        let _ = remove_file(server_dir.join(&file_name));
+        let _ = remove_file(server_dir.join(format!("{}.map", file_name)));
+    }
+}
+
+/// Files watched by the development server.
+enum Watch {
+    /// Directories or files, which are walked again on each scan.
+    Inputs(Vec<PathBuf>, InputFilter),
+
+    /// Source files of a `tsconfig.json`, which are listed when the server
+    /// starts. `selected` are the ones selected by the include globs.
+    Project {
+        files: Arc<Vec<PathBuf>>,
+        selected: Vec<PathBuf>,
+    },
+}
+
+impl Watch {
+    /// Returns the last modification time of all watched files, including
+    /// helper modules.
+    fn scan(&self) -> Result<BTreeMap<PathBuf, Option<SystemTime>>> {
+        let files = match self {
+            Watch::Inputs(inputs, filter) => walk_inputs(inputs.clone(), filter)?,
+            Watch::Project { files, .. } => files.to_vec(),
+        };
+
+        Ok(files
+            .into_iter()
+            .map(|path| {
+                let modified = path.metadata().and_then(|v| v.modified()).ok();
+                (path, modified)
+            })
+            .collect())
+    }
+
+    /// Returns true if `path` is compiled if it's an api file.
+    fn is_selected(&self, path: &Path) -> bool {
+        match self {
+            Watch::Inputs(..) => true,
+            Watch::Project { selected, .. } => selected.iter().any(|p| p == path),
+        }
     }
 }
@@ -0,0 +1,215 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+
+use crate::commands::{ClientType, Target};
+
+/// Names of the configuration files, in the order of priority.
+const CONFIG_FILE_NAMES: &[&str] = &["fnapi.config.json", "fnapi.toml"];
+
+/// Per-project configuration, loaded from `fnapi.config.json` or
+/// `fnapi.toml`.
+///
+/// Options passed as command line flags take precedence over this.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub(crate) struct FnApiConfig {
+    /// Directories or files to build.
+    #[serde(default)]
+    pub inputs: Vec<PathBuf>,
+
+    /// Path to a `tsconfig.json`, used instead of `inputs`.
+    #[serde(default)]
+    pub project: Option<PathBuf>,
+
+    /// Glob patterns to select api files. All files are selected if empty.
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    #[serde(default)]
+    pub server_target: Option<Target>,
+
+    #[serde(default)]
+    pub client_types: Vec<ClientType>,
+
+    #[serde(default)]
+    pub client_target_dir: Option<PathBuf>,
+
+    #[serde(default)]
+    pub fnapi_dir: Option<PathBuf>,
+
+    /// Prefix for the path of all routes, like `/api`.
+    #[serde(default)]
+    pub route_prefix: Option<String>,
+
+    /// Glob patterns for input files to ignore.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+
+    /// Directory of the configuration file, which `include` and `exclude` are
+    /// relative to.
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+impl FnApiConfig {
+    /// Loads the nearest configuration file in `dir` or its ancestors, if any.
+    ///
+    /// Paths of the configuration are resolved relative to `dir`, like
+    /// `../src`.
+    pub fn discover(dir: &Path) -> Result<Self> {
+        let mut root = PathBuf::new();
+
+        for ancestor in dir.ancestors() {
+            for name in CONFIG_FILE_NAMES {
+                let path = ancestor.join(name);
+
+                if path.is_file() {
+                    return Ok(Self::read(&path)?.with_root(root));
+                }
+            }
+
+            root.push("..");
+        }
+
+        Ok(Default::default())
+    }
+
+    /// Loads the configuration file at `path`, resolving its paths relative
+    /// to the directory of the file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let root = path.parent().unwrap_or_else(|| Path::new("")).to_path_buf();
+
+        Ok(Self::read(path)?.with_root(root))
+    }
+
+    fn read(path: &Path) -> Result<Self> {
+        let s = read_to_string(path)
+            .with_context(|| format!("failed to read config file `{}`", path.display()))?;
+
+        if path.extension().map_or(false, |ext| ext == "toml") {
+            toml::from_str(&s)
+                .with_context(|| format!("failed to parse config file `{}`", path.display()))
+        } else {
+            serde_json::from_str(&s)
+                .with_context(|| format!("failed to parse config file `{}`", path.display()))
+        }
+    }
+
+    /// Makes paths of the configuration relative to `root` instead of the
+    /// directory of the configuration file.
+    fn with_root(mut self, root: PathBuf) -> Self {
+        for input in &mut self.inputs {
+            *input = root.join(&*input);
+        }
+        for path in [
+            &mut self.project,
+            &mut self.client_target_dir,
+            &mut self.fnapi_dir,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *path = root.join(&*path);
+        }
+
+        self.root = root;
+        self
+    }
+
+    /// Normalized route prefix, which starts with `/` and does not end with
+    /// `/`.
+    pub fn route_prefix(&self) -> Option<String> {
+        let prefix = self.route_prefix.as_deref()?.trim_matches('/');
+
+        if prefix.is_empty() {
+            None
+        } else {
+            Some(format!("/{}", prefix))
+        }
+    }
+
+    pub fn input_filter(&self) -> Result<InputFilter> {
+        InputFilter::new(&self.root, &self.include, &self.exclude)
+    }
+}
+
+/// Include and exclude globs for input files.
+pub(crate) struct InputFilter {
+    /// Paths are matched relative to this directory.
+    root: PathBuf,
+    includes: GlobSet,
+    excludes: GlobSet,
+}
+
+impl InputFilter {
+    pub fn new(root: &Path, include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            root: root.to_path_buf(),
+            includes: build_glob_set(include)?,
+            excludes: build_glob_set(exclude)?,
+        })
+    }
+
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        self.excludes.is_match(self.relative(path))
+    }
+
+    /// Returns true if `path` is included and not excluded.
+    pub fn is_match(&self, path: &Path) -> bool {
+        (self.includes.is_empty() || self.includes.is_match(self.relative(path)))
+            && !self.is_excluded(path)
+    }
+
+    fn relative<'a>(&self, path: &'a Path) -> &'a Path {
+        path.strip_prefix(&self.root).unwrap_or(path)
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).with_context(|| format!("invalid glob `{}`", pattern))?);
+    }
+
+    builder.build().context("failed to build glob set")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::FnApiConfig;
+
+    #[test]
+    fn discover_in_ancestors() {
+        let config = FnApiConfig::discover(Path::new("tests/config/1/src/api")).unwrap();
+
+        assert_eq!(config.inputs, vec![PathBuf::from("../../src")]);
+        assert_eq!(config.fnapi_dir, Some(PathBuf::from("../../.fnapi")));
+
+        let filter = config.input_filter().unwrap();
+        assert!(filter.is_match(Path::new("../../src/api/todo.api.ts")));
+        assert!(!filter.is_match(Path::new("../../src/api/todo.ts")));
+    }
+
+    #[test]
+    fn load_relative_to_config_file() {
+        let config = FnApiConfig::load(Path::new("tests/config/1/fnapi.toml")).unwrap();
+
+        assert_eq!(config.inputs, vec![PathBuf::from("tests/config/1/src")]);
+        assert_eq!(
+            config.fnapi_dir,
+            Some(PathBuf::from("tests/config/1/.fnapi"))
+        );
+
+        let filter = config.input_filter().unwrap();
+        assert!(filter.is_match(Path::new("tests/config/1/src/api/todo.api.ts")));
+    }
+}
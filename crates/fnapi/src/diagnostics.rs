@@ -0,0 +1,293 @@
+//! Machine-readable diagnostics.
+
+use std::{
+    env::current_dir,
+    io::{stdout, Write},
+    mem::take,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, Context, Result};
+use clap::ArgEnum;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use swc_common::{
+    errors::{Diagnostic, DiagnosticBuilder, DiagnosticId, Emitter, Level},
+    Loc, MultiSpan, SourceMap,
+};
+use tower_lsp::lsp_types::Url;
+
+#[derive(ArgEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DiagnosticsFormat {
+    /// Human readable output, written to stderr
+    Pretty,
+
+    /// One JSON object per diagnostic, written to stdout
+    Json,
+
+    /// A SARIF 2.1.0 log, written to stdout when the command finishes
+    Sarif,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DiagnosticRecord {
+    pub level: &'static str,
+    pub code: Option<String>,
+    pub message: String,
+    pub file: Option<String>,
+    pub span: Option<SpanRecord>,
+    pub notes: Vec<NoteRecord>,
+    pub help: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NoteRecord {
+    pub message: String,
+    pub file: Option<String>,
+    pub span: Option<SpanRecord>,
+}
+
+/// Lines and columns are 1-based. Columns are counted in UTF-16 code units,
+/// like LSP and SARIF do.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SpanRecord {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl DiagnosticRecord {
+    fn new(cm: &SourceMap, d: &Diagnostic) -> Self {
+        let (file, span) = locate(cm, &d.span);
+
+        let mut notes = vec![];
+        let mut help = vec![];
+
+        for child in &d.children {
+            match child.level {
+                Level::Help => help.push(child.message()),
+                _ => {
+                    let (file, span) = locate(cm, &child.span);
+
+                    notes.push(NoteRecord {
+                        message: child.message(),
+                        file,
+                        span,
+                    });
+                }
+            }
+        }
+
+        Self {
+            level: match d.level {
+                Level::Bug | Level::Fatal | Level::PhaseFatal | Level::Error => "error",
+                Level::Warning => "warning",
+                Level::Help => "help",
+                _ => "note",
+            },
+            code: d.code.as_ref().map(|code| match code {
+                DiagnosticId::Error(code) | DiagnosticId::Lint(code) => code.clone(),
+            }),
+            message: d.message(),
+            file,
+            span,
+            notes,
+            help,
+        }
+    }
+}
+
+fn locate(cm: &SourceMap, span: &MultiSpan) -> (Option<String>, Option<SpanRecord>) {
+    let span = match span.primary_span() {
+        Some(span) if !span.is_dummy() => span,
+        _ => return (None, None),
+    };
+
+    let lo = cm.lookup_char_pos(span.lo);
+    let hi = cm.lookup_char_pos(span.hi);
+
+    (
+        Some(lo.file.name.to_string()),
+        Some(SpanRecord {
+            start_line: lo.line,
+            start_column: utf16_col(&lo) + 1,
+            end_line: hi.line,
+            end_column: utf16_col(&hi) + 1,
+        }),
+    )
+}
+
+/// Returns the 0-based column of `loc` in UTF-16 code units.
+pub(crate) fn utf16_col(loc: &Loc) -> usize {
+    match loc.file.get_line(loc.line - 1) {
+        Some(line) => line.chars().take(loc.col.0).map(char::len_utf16).sum(),
+        None => loc.col.0,
+    }
+}
+
+/// Writes each diagnostic as a line of JSON to stdout.
+pub(crate) struct JsonEmitter {
+    cm: Arc<SourceMap>,
+}
+
+impl JsonEmitter {
+    pub fn new(cm: Arc<SourceMap>) -> Self {
+        Self { cm }
+    }
+}
+
+impl Emitter for JsonEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        let record = DiagnosticRecord::new(&self.cm, db);
+
+        let mut out = stdout().lock();
+        let _ = serde_json::to_writer(&mut out, &record);
+        let _ = writeln!(out);
+    }
+}
+
+/// Adds diagnostics to [CollectedDiagnostics].
+pub(crate) struct CollectingEmitter {
+    cm: Arc<SourceMap>,
+    records: Arc<Mutex<Vec<DiagnosticRecord>>>,
+}
+
+impl Emitter for CollectingEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        let record = DiagnosticRecord::new(&self.cm, db);
+
+        self.records.lock().unwrap().push(record);
+    }
+}
+
+/// Diagnostics collected by [CollectingEmitter]s.
+#[derive(Clone, Default)]
+pub(crate) struct CollectedDiagnostics {
+    records: Arc<Mutex<Vec<DiagnosticRecord>>>,
+}
+
+impl CollectedDiagnostics {
+    pub fn emitter(&self, cm: Arc<SourceMap>) -> CollectingEmitter {
+        CollectingEmitter {
+            cm,
+            records: self.records.clone(),
+        }
+    }
+
+    pub fn take(&self) -> Vec<DiagnosticRecord> {
+        take(&mut *self.records.lock().unwrap())
+    }
+
+    /// Prints the diagnostics to stdout as a SARIF log.
+    pub fn print_sarif(&self) -> Result<()> {
+        // Locations are relative to the working directory, so the log does
+        // not depend on where the project is.
+        let cwd = current_dir().context("failed to get the current directory")?;
+        let root = Url::from_directory_path(&cwd)
+            .map_err(|()| anyhow!("failed to convert {} to a URI", cwd.display()))?;
+
+        let results = self
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|record| sarif_result(&root, record))
+            .collect::<Vec<_>>();
+
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "fnapi",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    }
+                },
+                "originalUriBaseIds": {
+                    SRC_ROOT: { "uri": root.as_str() },
+                },
+                "results": results,
+            }],
+        });
+
+        let mut out = stdout().lock();
+        serde_json::to_writer_pretty(&mut out, &log).context("failed to print SARIF log")?;
+        writeln!(out).context("failed to print SARIF log")
+    }
+}
+
+/// Base of the URIs of artifacts, which is the working directory.
+const SRC_ROOT: &str = "%SRCROOT%";
+
+fn sarif_result(root: &Url, record: &DiagnosticRecord) -> Value {
+    let mut text = record.message.clone();
+    let mut related_locations = vec![];
+
+    for note in &record.notes {
+        match sarif_location(root, note.file.as_deref(), note.span.as_ref()) {
+            Some(mut location) => {
+                location["message"] = json!({ "text": note.message });
+                related_locations.push(location);
+            }
+            _ => {
+                text.push_str("\nnote: ");
+                text.push_str(&note.message);
+            }
+        }
+    }
+    for help in &record.help {
+        text.push_str("\nhelp: ");
+        text.push_str(help);
+    }
+
+    let mut result = Map::new();
+    if let Some(code) = &record.code {
+        result.insert("ruleId".into(), json!(code));
+    }
+    result.insert(
+        "level".into(),
+        json!(match record.level {
+            "error" | "warning" => record.level,
+            _ => "note",
+        }),
+    );
+    result.insert("message".into(), json!({ "text": text }));
+    if let Some(location) = sarif_location(root, record.file.as_deref(), record.span.as_ref()) {
+        result.insert("locations".into(), json!([location]));
+    }
+    if !related_locations.is_empty() {
+        result.insert("relatedLocations".into(), json!(related_locations));
+    }
+
+    Value::Object(result)
+}
+
+/// Returns [None] if the location is unknown, or the file is not a file of
+/// the disk.
+fn sarif_location(root: &Url, file: Option<&str>, span: Option<&SpanRecord>) -> Option<Value> {
+    let (file, span) = file.zip(span)?;
+
+    // A URI reference like `src/todo%20api.ts`.
+    let url = Url::from_file_path(current_dir().ok()?.join(file)).ok()?;
+    let uri = root.make_relative(&url)?;
+
+    Some(json!({
+        "physicalLocation": {
+            "artifactLocation": {
+                "uri": uri,
+                "uriBaseId": SRC_ROOT,
+            },
+            "region": {
+                "startLine": span.start_line,
+                "startColumn": span.start_column,
+                "endLine": span.end_line,
+                "endColumn": span.end_column,
+            },
+        },
+    }))
+}
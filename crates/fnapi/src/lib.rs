@@ -1,21 +1,44 @@
 #![feature(box_syntax)]
 
+mod cache;
 mod commands;
+mod config;
+mod diagnostics;
 
 use std::{
+    env::current_dir,
     fmt,
     io::{stderr, Write},
+    path::PathBuf,
     sync::Arc,
 };
 
+use anyhow::Context;
 use clap::{Parser, Subcommand};
 use fnapi_core::Env;
-use swc_common::{errors::Handler, SourceMap};
+use swc_common::{
+    errors::{Emitter, Handler},
+    Globals, SourceMap,
+};
 use swc_error_reporters::{GraphicalReportHandler, PrettyEmitter, PrettyEmitterConfig};
 
+use crate::{
+    config::FnApiConfig,
+    diagnostics::{CollectedDiagnostics, DiagnosticsFormat, JsonEmitter},
+};
+
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
 pub struct Args {
+    /// Path to the configuration file. Defaults to `fnapi.config.json` or
+    /// `fnapi.toml` in the current directory.
+    #[clap(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Format of diagnostics.
+    #[clap(arg_enum, long, global = true, default_value = "pretty")]
+    diagnostics_format: DiagnosticsFormat,
+
     #[clap(subcommand)]
     cmd: Command,
 }
@@ -23,6 +46,11 @@ pub struct Args {
 #[derive(Subcommand, Debug)]
 enum Command {
     Build(commands::BuildCommand),
+    Check(commands::CheckCommand),
+    Diff(commands::DiffCommand),
+    Lsp(commands::LspCommand),
+    #[clap(name = "openapi")]
+    OpenApi(commands::OpenApiCommand),
     Serve(commands::ServeCommand),
 }
 
@@ -30,27 +58,52 @@ impl Args {
     pub async fn run(self) -> anyhow::Result<()> {
         let cm = Arc::new(SourceMap::default());
 
+        let mut sarif_log = None;
+        let emitter: Box<dyn Emitter> = match self.diagnostics_format {
+            DiagnosticsFormat::Pretty => box PrettyEmitter::new(
+                cm.clone(),
+                box StderrWriter,
+                GraphicalReportHandler::new(),
+                PrettyEmitterConfig {
+                    skip_filename: false,
+                },
+            ),
+            DiagnosticsFormat::Json => box JsonEmitter::new(cm.clone()),
+            DiagnosticsFormat::Sarif => {
+                let collected = CollectedDiagnostics::default();
+                let emitter = collected.emitter(cm.clone());
+                sarif_log = Some(collected);
+                box emitter
+            }
+        };
+
         let env = Env {
-            cm: cm.clone(),
-            globals: Default::default(),
-            handler: Arc::new(Handler::with_emitter(
-                true,
-                false,
-                box PrettyEmitter::new(
-                    cm,
-                    box StderrWriter,
-                    GraphicalReportHandler::new(),
-                    PrettyEmitterConfig {
-                        skip_filename: false,
-                    },
-                ),
-            )),
+            cm,
+            globals: Arc::new(Globals::new()),
+            handler: Arc::new(Handler::with_emitter(true, false, emitter)),
+        };
+
+        let config = match &self.config {
+            Some(path) => FnApiConfig::load(path)?,
+            None => FnApiConfig::discover(
+                &current_dir().context("failed to get the current directory")?,
+            )?,
         };
 
-        match self.cmd {
-            Command::Build(cmd) => cmd.run(&env).await,
-            Command::Serve(cmd) => cmd.run(&env).await,
+        let result = match self.cmd {
+            Command::Build(cmd) => cmd.run(&env, &config).await,
+            Command::Check(cmd) => cmd.run(&env, &config).await,
+            Command::Diff(cmd) => cmd.run(&env).await,
+            Command::Lsp(cmd) => cmd.run(&env, &config).await,
+            Command::OpenApi(cmd) => cmd.run(&env, &config).await,
+            Command::Serve(cmd) => cmd.run(&env, &config).await,
+        };
+
+        if let Some(log) = sarif_log {
+            log.print_sarif()?;
         }
+
+        result
     }
 }
 
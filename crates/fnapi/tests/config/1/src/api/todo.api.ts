@@ -0,0 +1,8 @@
+import { FnApi } from '@fnapi/api';
+
+export default class TodoApi {
+    @FnApi()
+    static async get(id: string): Promise<string> {
+        return id;
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     fmt::{self, Debug, Formatter},
     hash::BuildHasherDefault,
+    mem::take,
     path::Path,
     sync::Arc,
 };
@@ -8,7 +9,10 @@ use std::{
 use anyhow::{Context, Result};
 use dashmap::DashMap;
 use rustc_hash::FxHasher;
-use swc_common::{FileName, Mark, SourceMap};
+use swc_common::{
+    comments::{SingleThreadedComments, SingleThreadedCommentsMapInner},
+    FileName, Mark, SourceFile, SourceMap,
+};
 use swc_ecma_transforms_base::resolver;
 use swc_ecmascript::{
     ast::{EsVersion, Module},
@@ -42,9 +46,13 @@ impl Default for ModuleMetadata {
     }
 }
 
+/// Leading comments of a module, keyed by the start of the commented node.
+pub type LeadingComments = SingleThreadedCommentsMapInner;
+
 #[derive(Debug)]
 struct ModuleData {
     content: Arc<Module>,
+    leading_comments: Arc<LeadingComments>,
 }
 
 impl Debug for Modules {
@@ -71,7 +79,43 @@ impl Modules {
         self.data.get(&key).map(|v| v.content.clone())
     }
 
+    /// Drops the parsed module for `path`, so the next [Modules::load] reads
+    /// it again from the disk.
+    ///
+    /// The `top_level_mark` of the module is preserved.
+    pub fn invalidate(&self, path: &Path) {
+        self.data
+            .remove(&Arc::new(FileName::Real(path.to_path_buf())));
+    }
+
+    /// Parses `text` as the content of `path`, which is used by
+    /// [Modules::load] instead of the file on the disk until the module is
+    /// invalidated.
+    pub fn update(&self, path: &Path, text: String) -> Result<()> {
+        let fm = self
+            .cm
+            .new_source_file(FileName::Real(path.to_path_buf()), text);
+
+        let key = Arc::new(fm.name.clone());
+        let top_level_mark = self.get_top_level_mark_for(key.clone());
+
+        match parse(&fm, path, self.unresolved_mark, top_level_mark) {
+            Ok(data) => {
+                self.data.insert(key, data);
+                Ok(())
+            }
+            Err(err) => {
+                self.data.remove(&key);
+                Err(err)
+            }
+        }
+    }
+
     pub fn load(&self, path: &Path) -> Result<Arc<Module>> {
+        self.load_with_comments(path).map(|(m, _)| m)
+    }
+
+    pub fn load_with_comments(&self, path: &Path) -> Result<(Arc<Module>, Arc<LeadingComments>)> {
         let fm = self
             .cm
             .load_file(path)
@@ -81,30 +125,45 @@ impl Modules {
         let unresolved_mark = self.unresolved_mark;
         let top_level_mark = self.get_top_level_mark_for(key.clone());
 
-        let e = self.data.entry(key).or_try_insert_with(|| -> Result<_> {
-            let tsx = path.to_string_lossy().ends_with(".tsx");
-            let dts = path.to_string_lossy().ends_with(".d.ts");
-            let mut m = parse_file_as_module(
-                &fm,
-                Syntax::Typescript(TsConfig {
-                    tsx,
-                    dts,
-                    decorators: true,
-                    ..Default::default()
-                }),
-                EsVersion::latest(),
-                None,
-                &mut vec![],
-            )
-            .map_err(|err| anyhow::anyhow!("failed to parse `{}`: {:?}", path.display(), err))?;
-
-            m.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, true));
-
-            Ok(ModuleData {
-                content: Arc::new(m),
-            })
-        })?;
-
-        Ok(e.content.clone())
+        let e = self
+            .data
+            .entry(key)
+            .or_try_insert_with(|| parse(&fm, path, unresolved_mark, top_level_mark))?;
+
+        Ok((e.content.clone(), e.leading_comments.clone()))
     }
 }
+
+fn parse(
+    fm: &SourceFile,
+    path: &Path,
+    unresolved_mark: Mark,
+    top_level_mark: Mark,
+) -> Result<ModuleData> {
+    let tsx = path.to_string_lossy().ends_with(".tsx");
+    let dts = path.to_string_lossy().ends_with(".d.ts");
+    let comments = SingleThreadedComments::default();
+    let mut m = parse_file_as_module(
+        fm,
+        Syntax::Typescript(TsConfig {
+            tsx,
+            dts,
+            decorators: true,
+            ..Default::default()
+        }),
+        EsVersion::latest(),
+        Some(&comments),
+        &mut vec![],
+    )
+    .map_err(|err| anyhow::anyhow!("failed to parse `{}`: {:?}", path.display(), err))?;
+
+    m.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, true));
+
+    let (leading, _) = comments.take_all();
+    let leading_comments = take(&mut *leading.borrow_mut());
+
+    Ok(ModuleData {
+        content: Arc::new(m),
+        leading_comments: Arc::new(leading_comments),
+    })
+}
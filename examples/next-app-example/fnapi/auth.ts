@@ -13,7 +13,7 @@ export default class AuthApi {
     path: "/logi",
   })
   @Validate("email", [validators.email])
-  @Validate("password", [validators.required, validators.minLenth(8)])
+  @Validate("password", [validators.required, validators.minLength(8)])
   static async login(email: string, password: string): Promise<string> {
     const user = Context.get<User>();
   }
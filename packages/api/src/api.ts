@@ -1,13 +1,33 @@
 export * from './api/param.js'
 
-export function FnApi(): MethodDecorator {
-    return (prototype, key, descriptor) => { }
+export type FnApiOptions = Record<string, unknown>;
 
+/**
+ * Marks a static method as an api.
+ */
+export function FnApi(options?: FnApiOptions): MethodDecorator;
+/**
+ * Marks an exported function as an api, like
+ * `export const login = FnApi(async (email: string): Promise<string> => {})`.
+ */
+export function FnApi<F extends (...args: any[]) => Promise<any>>(fn: F): F;
+export function FnApi<F extends (...args: any[]) => Promise<any>>(options: FnApiOptions, fn: F): F;
+export function FnApi(...args: any[]): any {
+    const fn = args[args.length - 1];
+    if (typeof fn === "function") {
+        return fn;
+    }
+    return (prototype: any, key: string | symbol, descriptor: PropertyDescriptor) => { }
 }
 
 
 export interface HttpApiOptions {
-
+    method?: "GET" | "POST" | "PUT" | "PATCH" | "DELETE";
+    /**
+     * Path of the route, like `/todos/:id`. Path parameters are bound to
+     * parameters with the same name.
+     */
+    path?: string;
 }
 
 export function Http(options: HttpApiOptions): MethodDecorator {
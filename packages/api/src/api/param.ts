@@ -46,4 +46,12 @@ export interface HeaderOptions extends BaseParamOptions {
 
 export function Header(options?: HeaderOptions): ParameterDecorator {
     return impl(ParamKind.Header);
+}
+
+export interface BodyOptions extends BaseParamOptions {
+
+}
+
+export function Body(options?: BodyOptions): ParameterDecorator {
+    return impl(ParamKind.Body);
 }
\ No newline at end of file
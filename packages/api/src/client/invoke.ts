@@ -0,0 +1,186 @@
+/**
+ * Part of the request which carries a parameter.
+ */
+export type ParameterLocation = "body" | "query" | "path" | "header";
+
+/**
+ * Route of an api, passed by generated clients if it's not the default
+ * `POST /{ClassName}/{name}`.
+ */
+export interface InvokeOptions {
+  /**
+   * Prefix for the path of all routes, like `/api`.
+   */
+  readonly prefix?: string;
+  readonly method?: string;
+  /**
+   * Path template set by `@Http`, like `/todos/:id`.
+   */
+  readonly path?: string;
+  /**
+   * Locations of parameters which are not sent in the body, keyed by the
+   * names of parameters.
+   */
+  readonly parameterLocations?: Record<string, ParameterLocation>;
+}
+
+export interface HttpRequest {
+  readonly method: string;
+  readonly url: string;
+  readonly headers: Record<string, string>;
+  readonly body?: string;
+}
+
+export interface HttpResponse {
+  readonly status: number;
+  readonly body: string;
+}
+
+/**
+ * Sends a request, and resolves with the response even if it's an error.
+ */
+export type Send = (req: HttpRequest) => Promise<HttpResponse>;
+
+/**
+ * Thrown if the server responds with an error.
+ */
+export class ApiError extends Error {
+  constructor(
+    message: string,
+    readonly status: number,
+    readonly body: unknown
+  ) {
+    super(message);
+  }
+}
+
+/**
+ * Converts a parameter to a value of a query string or a header. Objects are
+ * sent as JSON.
+ */
+function stringify(value: unknown): string {
+  return typeof value === "object" ? JSON.stringify(value) : String(value);
+}
+
+/**
+ * Creates the request of an api, sending each parameter where
+ * `options.parameterLocations` says. Omitted parameters are not sent.
+ *
+ * Path parameters are bound to the segments of `options.path` with the same
+ * name, or appended to the default path. Body parameters of `GET` requests
+ * are sent in the query string, as such requests can't have a body.
+ */
+export function createRequest(
+  baseUrl: string,
+  className: string,
+  name: string,
+  params: Record<string, unknown>,
+  options: InvokeOptions = {}
+): HttpRequest {
+  const method = options.method ?? "POST";
+  const locations = options.parameterLocations ?? {};
+  let path = options.path ?? `/${className}/${name}`;
+  const query = new URLSearchParams();
+  const headers: Record<string, string> = {};
+  const body: Record<string, unknown> = {};
+  let hasBody = false;
+
+  for (const [key, value] of Object.entries(params)) {
+    const location = locations[key] ?? "body";
+
+    if (location === "path") {
+      // Path parameters are required, as the route does not match without
+      // them.
+      if (value === undefined) {
+        throw new Error(`${key} is required`);
+      }
+      const segment = encodeURIComponent(stringify(value));
+      if (options.path !== undefined) {
+        path = path
+          .split("/")
+          .map((part) => (part === `:${key}` ? segment : part))
+          .join("/");
+      } else {
+        path += `/${segment}`;
+      }
+      continue;
+    }
+
+    if (value === undefined) {
+      continue;
+    }
+
+    switch (location === "body" && method === "GET" ? "query" : location) {
+      case "query":
+        for (const item of Array.isArray(value) ? value : [value]) {
+          query.append(key, stringify(item));
+        }
+        break;
+      case "header":
+        headers[key] = stringify(value);
+        break;
+      case "body":
+        body[key] = value;
+        hasBody = true;
+        break;
+    }
+  }
+
+  const search = query.toString();
+  if (hasBody) {
+    headers["content-type"] = "application/json";
+  }
+
+  return {
+    method,
+    url: `${baseUrl}${options.prefix ?? ""}${path}${search ? `?${search}` : ""}`,
+    headers,
+    body: hasBody ? JSON.stringify(body) : undefined,
+  };
+}
+
+/**
+ * Client used by generated codes to call apis.
+ */
+export class FnApiClient {
+  constructor(
+    private readonly send: Send,
+    /**
+     * Origin of the server, like `https://example.com`.
+     */
+    public baseUrl: string = ""
+  ) {}
+
+  /**
+   * @internal This is helper for generated codes.
+   */
+  async invoke(
+    className: string,
+    name: string,
+    params: Record<string, unknown>,
+    options?: InvokeOptions
+  ): Promise<any> {
+    const res = await this.send(
+      createRequest(this.baseUrl, className, name, params, options)
+    );
+
+    let body: any = undefined;
+    if (res.body !== "") {
+      try {
+        body = JSON.parse(res.body);
+      } catch {
+        body = res.body;
+      }
+    }
+
+    if (res.status < 200 || res.status >= 300) {
+      throw new ApiError(
+        body?.message ?? `${className}.${name} failed with ${res.status}`,
+        res.status,
+        body
+      );
+    }
+
+    return body;
+  }
+}
@@ -0,0 +1,32 @@
+import { request as httpRequest } from "http";
+import { request as httpsRequest } from "https";
+
+import { FnApiClient } from "./invoke.js";
+
+/**
+ * Client of generated apis for Node.js. Requests are sent to
+ * `FNAPI_BASE_URL`, which defaults to the address of `fnapi serve`, unless
+ * `baseUrl` is set.
+ */
+const client = new FnApiClient(
+  ({ method, url, headers, body }) =>
+    new Promise((resolve, reject) => {
+      const target = new URL(url);
+      const request = target.protocol === "https:" ? httpsRequest : httpRequest;
+
+      const req = request(target, { method, headers }, (res) => {
+        let text = "";
+        res.setEncoding("utf8");
+        res.on("data", (chunk) => (text += chunk));
+        res.on("end", () =>
+          resolve({ status: res.statusCode ?? 0, body: text })
+        );
+        res.on("error", reject);
+      });
+      req.on("error", reject);
+      req.end(body);
+    }),
+  process.env.FNAPI_BASE_URL ?? "http://localhost:4321"
+);
+
+export default client;
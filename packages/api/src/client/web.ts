@@ -0,0 +1,12 @@
+import { FnApiClient } from "./invoke.js";
+
+/**
+ * Client of generated apis for browsers. Requests are sent to the origin of
+ * the page unless `baseUrl` is set.
+ */
+const client = new FnApiClient(async ({ method, url, headers, body }) => {
+  const res = await fetch(url, { method, headers, body });
+  return { status: res.status, body: await res.text() };
+});
+
+export default client;
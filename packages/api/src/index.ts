@@ -4,9 +4,16 @@ export * from "./api.js";
 export * from "./context.js";
 export * as validators from "./validators/index.js";
 
+/**
+ * Validates the parameter named `fieldName`.
+ *
+ * `required`, `email`, `minLength` and `maxLength` of `validators` are
+ * compiled into the JSON schema of the parameter, and checked by the
+ * generated clients too.
+ */
 export function Validate<T>(
   fieldName: string,
   validators: Validator<T>[]
-): ParameterDecorator {
+): MethodDecorator {
   return () => {};
 }
@@ -7,15 +7,70 @@ type JsonSchema = object;
 export interface MethodDescriptor {
   readonly name: string;
   readonly httpMethod: string;
+  /**
+   * Path template set by `@Http`, like `/todos/:id`.
+   */
+  readonly path?: string;
 
   readonly returnType: JsonSchema;
+  /**
+   * Copies the fields declared in the return type, so other fields of the
+   * returned value are not sent. Omitted if the return type has no object or
+   * tuple.
+   */
+  readonly projectResponse?: (value: any) => any;
+  /**
+   * Names of parameters, used as keys of the request. Unnamed parameters are
+   * named `p{idx}`.
+   */
+  readonly parameterNames: string[];
+  /**
+   * Names of parameters which may be omitted, like `limit?: number` or
+   * `limit = 10`.
+   */
+  readonly optionalParameterNames?: string[];
   readonly parameterTypes: JsonSchema[];
+
+  /**
+   * Object schemas of parameters which are not in the body, keyed by the
+   * names of parameters.
+   */
+  readonly queryStringJsonSchema?: JsonSchema;
+  readonly paramsJsonSchema?: JsonSchema;
+  readonly headersJsonSchema?: JsonSchema;
+}
+
+export interface WrapOptions {
+  /**
+   * Prefix for the path of all routes, like `/api`.
+   */
+  readonly prefix?: string;
+
+  /**
+   * Name used for routes instead of the class name. Used for exported
+   * functions, which are routed by the name of the file.
+   */
+  readonly name?: string;
+}
+
+function keysOf(schema?: JsonSchema): string[] {
+  return schema ? Object.keys((schema as any).properties) : [];
 }
 
-function parseParams(params: object): any[] {
-  const arr = new Array(Object.keys(params).length);
-  for (const [key, value] of Object.entries(params)) {
-    arr[parseInt(key.substring(1))] = value;
+/**
+ * Collects parameters from each part of the request, in the order of
+ * `names`.
+ */
+function parseParams(
+  names: string[],
+  sources: [source: any, keys: string[]][]
+): any[] {
+  const arr = new Array(names.length);
+  for (const [source, keys] of sources) {
+    for (const key of keys) {
+      // Node.js lowercases names of headers.
+      arr[names.indexOf(key)] = source?.[key] ?? source?.[key.toLowerCase()];
+    }
   }
   return arr;
 }
@@ -25,22 +80,40 @@ function parseParams(params: object): any[] {
  */
 export default function wrapApiClass(
   cls: any,
-  methods: MethodDescriptor[]
+  methods: MethodDescriptor[],
+  options: WrapOptions = {}
 ): (fastify: FastifyInstance) => void {
-  const className = cls.name as string;
+  const className = options.name ?? (cls.name as string);
   if (!className) {
     throw new Error(`@FnApi requires a named class`);
   }
 
   return (fastify: FastifyInstance) => {
     for (const methodDesc of methods) {
+      const queryKeys = keysOf(methodDesc.queryStringJsonSchema);
+      const pathKeys = keysOf(methodDesc.paramsJsonSchema);
+      const headerKeys = keysOf(methodDesc.headersJsonSchema);
+
+      const bodyProperties = methodDesc.parameterTypes
+        .map((ty, idx) => [methodDesc.parameterNames[idx], ty] as const)
+        .filter(
+          ([key]) =>
+            !queryKeys.includes(key) &&
+            !pathKeys.includes(key) &&
+            !headerKeys.includes(key)
+        );
+      const bodyKeys = bodyProperties.map(([key]) => key);
+      const requiredBodyKeys = bodyKeys.filter(
+        (key) => !methodDesc.optionalParameterNames?.includes(key)
+      );
       const bodyJsonSchema =
-        methodDesc.parameterTypes.length > 0
+        bodyProperties.length > 0
           ? {
               type: "object",
-              properties: Object.fromEntries(
-                methodDesc.parameterTypes.map((ty, idx) => [`p${idx}`, ty])
-              ),
+              properties: Object.fromEntries(bodyProperties),
+              ...(requiredBodyKeys.length > 0
+                ? { required: requiredBodyKeys }
+                : {}),
             }
           : undefined;
 
@@ -52,15 +125,29 @@ export default function wrapApiClass(
       console.log(`Response:`, JSON.stringify(responseSchema));
 
       fastify.route({
-        // TODO:
-        method: "POST",
-        url: `/${className}/${methodDesc.name}`,
+        method: methodDesc.httpMethod,
+        url:
+          methodDesc.path !== undefined
+            ? `${options.prefix ?? ""}${methodDesc.path}`
+            : `${options.prefix ?? ""}/${className}/${methodDesc.name}${pathKeys
+                .map((key) => `/:${key}`)
+                .join("")}`,
         schema: {
           body: bodyJsonSchema,
+          querystring: methodDesc.queryStringJsonSchema,
+          params: methodDesc.paramsJsonSchema,
+          headers: methodDesc.headersJsonSchema,
           response: responseSchema,
         },
         handler: async (req, reply) => {
-          const params = Object.freeze(parseParams(req.body as object));
+          const params = Object.freeze(
+            parseParams(methodDesc.parameterNames, [
+              [req.body, bodyKeys],
+              [req.query, queryKeys],
+              [req.params, pathKeys],
+              [req.headers, headerKeys],
+            ])
+          );
 
           const fReq: FnApiRequest = {
             raw: req,
@@ -75,7 +162,11 @@ export default function wrapApiClass(
           const handler = cls[methodDesc.name] as FnApiHandler;
           const returnValue = await handler.call(cls, fReq, fReply);
 
-          reply.send(returnValue);
+          reply.send(
+            methodDesc.projectResponse
+              ? methodDesc.projectResponse(returnValue)
+              : returnValue
+          );
         },
       });
     }
@@ -3,9 +3,11 @@ import { MethodDescriptor } from "./wrapApiClass";
 export type WrappedConfig = Omit<
   MethodDescriptor,
   | "name"
+  | "path"
   | "filterReturnValue"
   | "bodyJsonSchema"
   | "queryStringJsonSchema"
+  | "paramsJsonSchema"
   | "headersJsonSchema"
 >;
 
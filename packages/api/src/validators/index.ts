@@ -23,7 +23,7 @@ class CoreValidator<T> implements Validator<T> {
 
 export const required: CoreValidator<any> = new CoreValidator(
   (fieldName, val: any) => {
-    if (val === "") {
+    if (val === undefined || val === null || val === "") {
       return new Error(`${fieldName} is required`);
     }
   }
@@ -60,3 +60,25 @@ export function maxLength(length: number): CoreValidator<string> {
     }
   });
 }
+
+/**
+ * Throws the first error of `validators`. Used by generated clients to check
+ * parameters before sending a request.
+ */
+export function check<T>(
+  fieldName: string,
+  value: T,
+  validators: Validator<T>[]
+): void {
+  // Omitted optional parameters are not validated, unless they are required.
+  if (value === undefined) {
+    validators = validators.filter((validator) => validator === required);
+  }
+
+  for (const validator of validators) {
+    const err = validator.validate(fieldName, value);
+    if (err) {
+      throw err;
+    }
+  }
+}
@@ -0,0 +1,156 @@
+import {
+  ApiError,
+  createRequest,
+  FnApiClient,
+  HttpRequest,
+  HttpResponse,
+} from "../src/client/invoke";
+
+describe("createRequest", () => {
+  it("sends parameters in the body by default", () => {
+    expect(
+      createRequest("", "TodoApi", "create", { title: "a", tags: undefined })
+    ).toEqual({
+      method: "POST",
+      url: "/TodoApi/create",
+      headers: { "content-type": "application/json" },
+      body: '{"title":"a"}',
+    });
+  });
+
+  it("prepends the base url and the prefix", () => {
+    const req = createRequest("http://localhost:4321", "TodoApi", "list", {}, {
+      prefix: "/api",
+    });
+
+    expect(req.url).toBe("http://localhost:4321/api/TodoApi/list");
+    expect(req.body).toBeUndefined();
+    expect(req.headers).toEqual({});
+  });
+
+  it("sends parameters where they belong", () => {
+    const req = createRequest(
+      "",
+      "TodoApi",
+      "update",
+      { id: "a/b", done: true, tags: ["x", "y"], token: "t", title: "c" },
+      {
+        parameterLocations: {
+          id: "path",
+          done: "query",
+          tags: "query",
+          token: "header",
+        },
+      }
+    );
+
+    expect(req).toEqual({
+      method: "POST",
+      url: "/TodoApi/update/a%2Fb?done=true&tags=x&tags=y",
+      headers: { token: "t", "content-type": "application/json" },
+      body: '{"title":"c"}',
+    });
+  });
+
+  it("binds path parameters to the path template", () => {
+    const req = createRequest(
+      "",
+      "TodoApi",
+      "get",
+      { listId: "l 1", id: "1", fields: "title" },
+      {
+        prefix: "/api",
+        method: "GET",
+        path: "/lists/:listId/todos/:id",
+        parameterLocations: { listId: "path", id: "path" },
+      }
+    );
+
+    expect(req.method).toBe("GET");
+    expect(req.url).toBe("/api/lists/l%201/todos/1?fields=title");
+  });
+
+  it("sends parameters of GET requests in the query string", () => {
+    expect(
+      createRequest(
+        "",
+        "TodoApi",
+        "search",
+        { text: "milk", limit: 10, done: undefined },
+        { method: "GET" }
+      )
+    ).toEqual({
+      method: "GET",
+      url: "/TodoApi/search?text=milk&limit=10",
+      headers: {},
+      body: undefined,
+    });
+  });
+
+  it("sends a body for other methods", () => {
+    const req = createRequest(
+      "",
+      "TodoApi",
+      "remove",
+      { id: "1", reason: "done" },
+      {
+        method: "DELETE",
+        path: "/todos/:id",
+        parameterLocations: { id: "path" },
+      }
+    );
+
+    expect(req.url).toBe("/todos/1");
+    expect(req.body).toBe('{"reason":"done"}');
+  });
+
+  it("requires path parameters", () => {
+    expect(() =>
+      createRequest("", "TodoApi", "get", { id: undefined }, {
+        parameterLocations: { id: "path" },
+      })
+    ).toThrow("id is required");
+  });
+});
+
+describe("FnApiClient", () => {
+  function clientOf(res: HttpResponse): [FnApiClient, HttpRequest[]] {
+    const sent: HttpRequest[] = [];
+    const client = new FnApiClient(async (req) => {
+      sent.push(req);
+      return res;
+    }, "http://localhost");
+    return [client, sent];
+  }
+
+  it("resolves with the response", async () => {
+    const [client, sent] = clientOf({ status: 200, body: '{"id":"1"}' });
+
+    await expect(
+      client.invoke("TodoApi", "create", { title: "a" })
+    ).resolves.toEqual({ id: "1" });
+    expect(sent[0].url).toBe("http://localhost/TodoApi/create");
+  });
+
+  it("resolves with undefined for empty responses", async () => {
+    const [client] = clientOf({ status: 200, body: "" });
+
+    await expect(client.invoke("TodoApi", "remove", {})).resolves.toBe(
+      undefined
+    );
+  });
+
+  it("rejects with the error of the server", async () => {
+    const [client] = clientOf({
+      status: 400,
+      body: '{"statusCode":400,"error":"Bad Request","message":"body/title must be string"}',
+    });
+
+    const err = await client
+      .invoke("TodoApi", "create", { title: 1 })
+      .catch((e) => e);
+    expect(err).toBeInstanceOf(ApiError);
+    expect(err.message).toBe("body/title must be string");
+    expect(err.status).toBe(400);
+  });
+});
@@ -0,0 +1,23 @@
+import { check, minLength, required } from "../src/validators/index";
+
+describe("check", () => {
+  it("skips omitted optional parameters", () => {
+    expect(() => check("title", undefined, [minLength(3)])).not.toThrow();
+  });
+
+  it("rejects omitted required parameters", () => {
+    expect(() => check("title", undefined, [required, minLength(3)])).toThrow(
+      "title is required"
+    );
+  });
+
+  it("rejects empty strings for required parameters", () => {
+    expect(() => check("title", "", [required])).toThrow("title is required");
+  });
+
+  it("throws the first error", () => {
+    expect(() => check("title", "ab", [required, minLength(3)])).toThrow(
+      "title is too short"
+    );
+  });
+});